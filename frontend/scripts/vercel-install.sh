@@ -25,6 +25,6 @@ setup_rust() {
 
 setup_rust
 
-( cd ../shogi-core && npx --yes wasm-pack build --release )
+( cd ../shogi-core && npx --yes wasm-pack build --release -- --features serde )
 
 npm ci --include=dev || npm install --include=dev
@@ -39,7 +39,6 @@ export const CapturedPieces: React.FC<CapturedPiecesProps> = ({
       { piece: wasm.Piece.Gold, name: '金' },
       { piece: wasm.Piece.Bishop, name: '角' },
       { piece: wasm.Piece.Rook, name: '飛' },
-      { piece: wasm.Piece.King, name: '玉' },
     ];
     
     // pieceTypeは配列のインデックスなので、直接アクセス
@@ -64,8 +63,7 @@ export const CapturedPieces: React.FC<CapturedPiecesProps> = ({
       wasm.Piece.Silver,
       wasm.Piece.Gold,
       wasm.Piece.Bishop,
-      wasm.Piece.Rook,
-      wasm.Piece.King
+      wasm.Piece.Rook
     ];
     
     for (let i = 0; i < pieceTypes.length; i++) {
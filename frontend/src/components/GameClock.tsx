@@ -0,0 +1,81 @@
+import React, { useState } from 'react';
+import './GameClock.css';
+
+export interface ClockSettings {
+  mainMinutes: number;
+  byoyomiSeconds: number;
+  periods: number;
+  incrementSeconds: number;
+}
+
+interface GameClockProps {
+  clock: any | null;
+  now: number;
+  onStart: (settings: ClockSettings) => void;
+  onPause: () => void;
+  onResume: () => void;
+}
+
+// ミリ秒を "分:秒" にする
+const formatTime = (ms: number): string => {
+  const seconds = Math.ceil(Math.max(0, ms) / 1000);
+  return `${Math.floor(seconds / 60)}:${String(seconds % 60).padStart(2, '0')}`;
+};
+
+// 対局時計（持ち時間・秒読み・フィッシャーの設定と残り時間の表示）
+export const GameClock: React.FC<GameClockProps> = ({ clock, now, onStart, onPause, onResume }) => {
+  const wasm = (window as any).wasmModule;
+  const [settings, setSettings] = useState<ClockSettings>({
+    mainMinutes: 10,
+    byoyomiSeconds: 30,
+    periods: 1,
+    incrementSeconds: 0,
+  });
+
+  const updateSetting = (key: keyof ClockSettings) => (event: React.ChangeEvent<HTMLInputElement>) => {
+    const value = Math.max(0, Number(event.target.value) || 0);
+    setSettings((current) => ({ ...current, [key]: value }));
+  };
+
+  const renderSide = (player: any, label: string) => {
+    const remaining = clock.get_remaining(player, now);
+    const isTurn = clock.get_turn() === player && clock.get_loser() === undefined;
+    const inByoyomi = remaining <= 0 && clock.get_byoyomi_remaining(player, now) > 0;
+    return (
+      <div className={`clock-side ${isTurn ? 'clock-turn' : ''} ${clock.get_loser() === player ? 'clock-lost' : ''}`}>
+        <span className="clock-label">{label}</span>
+        <span className="clock-time">
+          {inByoyomi
+            ? `秒読み ${Math.ceil(clock.get_byoyomi_remaining(player, now) / 1000)}（残り${clock.get_periods_left(player, now)}回）`
+            : formatTime(remaining)}
+        </span>
+      </div>
+    );
+  };
+
+  if (!wasm) return null;
+
+  return (
+    <div className="game-clock">
+      {clock ? (
+        <>
+          {renderSide(wasm.Player.White, '△後手')}
+          {renderSide(wasm.Player.Black, '▲先手')}
+          {clock.get_loser() === undefined && (
+            clock.is_running()
+              ? <button className="clock-button" onClick={onPause}>一時停止</button>
+              : <button className="clock-button" onClick={onResume}>再開</button>
+          )}
+        </>
+      ) : (
+        <div className="clock-settings">
+          <label>持ち時間（分）<input type="number" value={settings.mainMinutes} onChange={updateSetting('mainMinutes')} /></label>
+          <label>秒読み（秒）<input type="number" value={settings.byoyomiSeconds} onChange={updateSetting('byoyomiSeconds')} /></label>
+          <label>秒読みの回数<input type="number" value={settings.periods} onChange={updateSetting('periods')} /></label>
+          <label>1手ごとの加算（秒）<input type="number" value={settings.incrementSeconds} onChange={updateSetting('incrementSeconds')} /></label>
+          <button className="clock-button" onClick={() => onStart(settings)}>対局時計を開始</button>
+        </div>
+      )}
+    </div>
+  );
+};
@@ -0,0 +1,77 @@
+import React, { useMemo } from 'react';
+import './CapturedPieces.css';
+
+interface PieceBoxProps {
+  board: any;
+  onDragStart?: (piece: any, event: React.DragEvent) => void;
+  onDrop?: (event: React.DragEvent) => void;
+  onDragOver?: (event: React.DragEvent) => void;
+  onTouchStart?: (piece: any, event: React.TouchEvent) => void;
+  onSendToHand?: (player: any) => void;
+}
+
+const PIECE_NAMES = ['歩', '香', '桂', '銀', '金', '角', '飛', '玉'];
+
+// 局面編集用の駒箱（盤上にも持ち駒にもない駒）
+export const PieceBox: React.FC<PieceBoxProps> = ({
+  board,
+  onDragStart,
+  onDrop,
+  onDragOver,
+  onTouchStart,
+  onSendToHand,
+}) => {
+  const wasm = (window as any).wasmModule;
+
+  const pieces = useMemo(() => {
+    if (!board || !wasm) return [];
+    // remaining_in_box() は歩香桂銀金角飛玉の順
+    const pieceTypes = [
+      wasm.Piece.Pawn,
+      wasm.Piece.Lance,
+      wasm.Piece.Knight,
+      wasm.Piece.Silver,
+      wasm.Piece.Gold,
+      wasm.Piece.Bishop,
+      wasm.Piece.Rook,
+      wasm.Piece.King,
+    ];
+    const remaining: number[] = Array.from(board.remaining_in_box());
+    return pieceTypes
+      .map((piece, i) => ({ piece, name: PIECE_NAMES[i], count: remaining[i] }))
+      .filter(entry => entry.count > 0);
+  }, [board, wasm]);
+
+  if (!wasm || !board) return null;
+
+  return (
+    <div className="captured-pieces-container piece-box">
+      <h3>駒箱</h3>
+      <div className="captured-pieces-list piece-box-list" onDrop={onDrop} onDragOver={onDragOver}>
+        {pieces.map(entry => (
+          <div
+            key={entry.piece}
+            className="captured-piece"
+            draggable
+            onDragStart={onDragStart ? (e) => onDragStart(entry.piece, e) : undefined}
+            onTouchStart={onTouchStart ? (e) => onTouchStart(entry.piece, e) : undefined}
+          >
+            <span className="piece-name">{entry.name}</span>
+            <span className="piece-count">×{entry.count}</span>
+          </div>
+        ))}
+        {pieces.length === 0 && <div className="no-pieces">駒箱は空です</div>}
+      </div>
+      {onSendToHand && pieces.some(entry => entry.piece !== wasm.Piece.King) && (
+        <div className="piece-box-actions">
+          <button className="edit-control-button" onClick={() => onSendToHand(wasm.Player.White)}>
+            残りを後手の持ち駒にする
+          </button>
+          <button className="edit-control-button" onClick={() => onSendToHand(wasm.Player.Black)}>
+            残りを先手の持ち駒にする
+          </button>
+        </div>
+      )}
+    </div>
+  );
+};
@@ -1,6 +1,8 @@
-import React, { useState, useEffect, useCallback } from 'react';
+import React, { useState, useEffect, useCallback, useMemo, useRef } from 'react';
 import { ErrorBoundary } from './ErrorBoundary';
 import { CapturedPieces } from './CapturedPieces';
+import { PieceBox } from './PieceBox';
+import { GameClock, ClockSettings } from './GameClock';
 import { BoardOcrDialog } from './BoardOcrDialog';
 import { applyRecognizedPosition } from '../utils/applyRecognizedPosition';
 import { RecognizedPosition } from '../utils/boardOcrTypes';
@@ -15,6 +17,126 @@ import './ShogiBoard.css';
 //   PieceInfo: any;
 // }
 
+// 駒落ちの種類（Handicap列挙の値。平手は「平手初期状態にする」で扱う）
+const HANDICAP_OPTIONS = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+
+// 局面の問題点（validate() の結果）をメッセージの一覧にする
+const getValidationMessages = (board: any): { message: string; isError: boolean }[] => {
+  if (!board || typeof board.validate !== 'function') return [];
+  return board.validate().map((issue: any) => ({
+    message: issue.get_message(),
+    isError: issue.is_error(),
+  }));
+};
+
+// 指した手の結果（MoveOutcome）で対局が終わっていれば、その知らせを返す
+const getGameEndMessage = (outcome: any): string | null => {
+  const wasm = window.wasmModule;
+  const status = outcome.get_status();
+  if (status === wasm.GameStatus.Ongoing) return null;
+  const winner = outcome.get_winner() === wasm.Player.Black ? '先手' : '後手';
+  switch (status) {
+    case wasm.GameStatus.Checkmate:
+      return `詰みです。${winner}の勝ちです。`;
+    case wasm.GameStatus.NoLegalMoves:
+      return `指せる手がありません。${winner}の勝ちです。`;
+    case wasm.GameStatus.Try:
+      return `トライしました。${winner}の勝ちです。`;
+    default:
+      return `玉を取りました。${winner}の勝ちです。`;
+  }
+};
+
+// 直前に指した側の浮き駒（hanging_pieces() の結果）を、初心者向けの注意の一覧にする
+const getHangingWarnings = (board: any): string[] => {
+  if (!board || typeof board.hanging_pieces !== 'function') return [];
+  const wasm = window.wasmModule;
+  const names: Record<number, string> = {
+    [wasm.Piece.Pawn]: '歩',
+    [wasm.Piece.Lance]: '香',
+    [wasm.Piece.Knight]: '桂',
+    [wasm.Piece.Silver]: '銀',
+    [wasm.Piece.Gold]: '金',
+    [wasm.Piece.Bishop]: '角',
+    [wasm.Piece.Rook]: '飛',
+    [wasm.Piece.PromotedPawn]: 'と',
+    [wasm.Piece.PromotedLance]: '成香',
+    [wasm.Piece.PromotedKnight]: '成桂',
+    [wasm.Piece.PromotedSilver]: '成銀',
+    [wasm.Piece.PromotedBishop]: '馬',
+    [wasm.Piece.PromotedRook]: '龍',
+  };
+  const kanjiNumbers = ['九', '八', '七', '六', '五', '四', '三', '二', '一'];
+  const mover = board.get_current_player() === wasm.Player.Black ? wasm.Player.White : wasm.Player.Black;
+  const symbol = mover === wasm.Player.Black ? '▲' : '△';
+  return board.hanging_pieces(mover).map((position: any) => {
+    const row = position.get_row();
+    const col = position.get_column();
+    const piece = board.get_piece_by_coords(row, col).piece;
+    return `${symbol}${9 - col}${kanjiNumbers[row]}の${names[piece] || '駒'}が取られそうです`;
+  });
+};
+
+// 両者の囲い（castles() のそろっている割合が最も高いもの）の表示名。組み途中なら何枚そろっているかを添える
+const getCastleLabels = (board: any): string[] => {
+  if (!board || typeof board.castles !== 'function') return [];
+  const wasm = window.wasmModule;
+  const sides: Array<[any, string]> = [[wasm.Player.Black, '▲先手'], [wasm.Player.White, '△後手']];
+  return sides.flatMap(([player, label]) => {
+    const [best] = board.castles(player);
+    if (!best) return [];
+    const matched = best.get_matched().length;
+    const total = matched + best.get_missing().length;
+    return [best.is_complete()
+      ? `${label}: ${best.get_name()}`
+      : `${label}: ${best.get_name()}（組み途中 ${matched}/${total}）`];
+  });
+};
+
+// 対局時計の時間切れを知らせる（loser は時間切れになった側）
+const announceTimeLoss = (loser: any) => {
+  const winner = loser === window.wasmModule.Player.Black ? '後手' : '先手';
+  setTimeout(() => alert(`時間切れです。${winner}の勝ちです。`), 0);
+};
+
+// 局面編集でドラッグ中の駒（盤上の駒はドラッグ開始時に駒箱へ移している）
+interface EditDrag {
+  piece: any;
+  player: any;
+  fromHand: boolean;
+}
+
+// 置く先のマスにある駒を、置く側の持ち駒にする（玉は駒箱へ）
+const clearDropTarget = (board: any, row: number, col: number, player: any) => {
+  if (board.get_piece_by_coords(row, col).piece === window.wasmModule.Piece.Empty) return;
+  if (!board.edit_board_to_hand(row, col, player)) {
+    board.edit_board_to_box(row, col);
+  }
+};
+
+const dropOnSquare = (board: any, drag: EditDrag, row: number, col: number) => {
+  clearDropTarget(board, row, col, drag.player);
+  if (drag.fromHand) {
+    board.edit_hand_to_board(drag.player, drag.piece, row, col);
+  } else {
+    board.edit_place_from_box(row, col, drag.piece, drag.player);
+  }
+};
+
+const dropOnHand = (board: any, drag: EditDrag, player: any) => {
+  if (drag.fromHand) {
+    board.edit_hand_to_hand(drag.player, player, drag.piece);
+  } else {
+    board.edit_box_to_hand(drag.piece, player);
+  }
+};
+
+const dropOnBox = (board: any, drag: EditDrag) => {
+  if (drag.fromHand) {
+    board.edit_hand_to_box(drag.player, drag.piece);
+  }
+};
+
 interface SquareProps {
   row: number;
   col: number;
@@ -36,9 +158,10 @@ interface SquareProps {
   onTouchStart?: (row: number, col: number, piece: any, player: any, event: React.TouchEvent) => void;
   onTouchMove?: (event: React.TouchEvent) => void;
   onTouchEnd?: (event: React.TouchEvent) => void;
+  attackCount?: { black: number; white: number }; // 利きの表示（先手・後手それぞれの利きの数）
 }
 
-const Square: React.FC<SquareProps> = ({ row, col, piece, player, isSelected, isValidMove, onClick, isDroppingMode, isEditMode, pieceState = 0, isBoardFlipped = false, isMoving, onDrop, onDragOver, onDragStart, onDragEnd, onDragLeave, onTouchStart, onTouchMove, onTouchEnd }) => {
+const Square: React.FC<SquareProps> = ({ row, col, piece, player, isSelected, isValidMove, onClick, isDroppingMode, isEditMode, pieceState = 0, isBoardFlipped = false, isMoving, onDrop, onDragOver, onDragStart, onDragEnd, onDragLeave, onTouchStart, onTouchMove, onTouchEnd, attackCount }) => {
   const wasm = (window as any).wasmModule;
   
   // 駒の状態に応じて表示を変更
@@ -179,6 +302,12 @@ const Square: React.FC<SquareProps> = ({ row, col, piece, player, isSelected, is
       onTouchEnd={handleTouchEndInternal}
     >
       {pieceText}
+      {attackCount && attackCount.black > 0 && (
+        <span className="attack-count attack-count-black">{attackCount.black}</span>
+      )}
+      {attackCount && attackCount.white > 0 && (
+        <span className="attack-count attack-count-white">{attackCount.white}</span>
+      )}
     </div>
   );
 };
@@ -199,7 +328,8 @@ const renderBoard = (
   onDragLeave?: (event: React.DragEvent) => void,
   onTouchStart?: (row: number, col: number, piece: any, player: any, event: React.TouchEvent) => void,
   onTouchMove?: (event: React.TouchEvent) => void,
-  onTouchEnd?: (event: React.TouchEvent) => void
+  onTouchEnd?: (event: React.TouchEvent) => void,
+  attackCounts?: number[] | null
 ) => {
   const squares = [];
   
@@ -291,6 +421,7 @@ const renderBoard = (
             onTouchStart={onTouchStart}
             onTouchMove={onTouchMove}
             onTouchEnd={onTouchEnd}
+            attackCount={attackCounts ? { black: attackCounts[row * 9 + col], white: attackCounts[81 + row * 9 + col] } : undefined}
           />
         );
       } catch (err) {
@@ -387,7 +518,14 @@ export const ShogiBoard: React.FC = () => {
   const [draggedPiece, setDraggedPiece] = useState<any>(null);
   const [draggedPlayer, setDraggedPlayer] = useState<any>(null);
   const [isBoardFlipped, setIsBoardFlipped] = useState(false);
+  const [showAttacks, setShowAttacks] = useState(false);
+  const [declarationRule, setDeclarationRule] = useState<'Points24' | 'Points27'>('Points27');
+  const [tryRule, setTryRule] = useState(false);
+  const clockRef = useRef<any>(null);
+  const [gameResult, setGameResult] = useState<any>(null); // 棋譜に書く終局（GameResult）
+  const [clockNow, setClockNow] = useState(() => Date.now());
   const [dragStartPosition, setDragStartPosition] = useState<{row: number, col: number} | null>(null);
+  const [isDraggingFromBox, setIsDraggingFromBox] = useState(false);
   const [pieceStates, setPieceStates] = useState<Record<string, number>>({});
   const [moveHistory, setMoveHistory] = useState<Array<{
     moveNumber: number;
@@ -400,10 +538,12 @@ export const ShogiBoard: React.FC = () => {
     toCol?: number;
     piece?: any; // 駒の種類
     isPromoted?: boolean; // 成り情報
+    consumedMs?: number; // 対局時計で計った消費時間
   }>>([]);
   const [currentMoveIndex, setCurrentMoveIndex] = useState(-1);
   const [isScrollLocked, setIsScrollLocked] = useState(false);
   const [uiVersion, setUiVersion] = useState(0);
+  const [moveText, setMoveText] = useState('');
   const [showOcrDialog, setShowOcrDialog] = useState(false);
   const touchDragRef = React.useRef<{ active: boolean } | null>(null);
 
@@ -536,37 +676,19 @@ export const ShogiBoard: React.FC = () => {
         }
         
         // 成り判定付きの移動を実行
-        console.log('make_move_by_coords_with_promotion呼び出し:', selectedRow, selectedCol, toRow, toCol, promote);
-        if (!newBoard.make_move_by_coords_with_promotion(selectedRow, selectedCol, toRow, toCol, promote)) {
+        const outcome = newBoard.make_move_with_outcome(selectedPosition, new window.wasmModule.Position(toRow, toCol), promote || false);
+        if (!outcome) {
           console.warn('移動の実行に失敗しました');
           return; // 移動に失敗した場合は現在の盤面を維持
         }
-        console.log('移動が成功しました');
-        
+        console.log('移動が成功しました:', outcome.get_usi(), outcome.is_capture() ? '（駒取り）' : '', outcome.is_check() ? '（王手）' : '');
 
-        
         // 指し手を記録（記録関数内で指し手を実行するため、ここでは記録のみ）
-        const pieceInfo = board.get_piece_by_coords(selectedRow, selectedCol);
-        recordMove(selectedRow, selectedCol, toRow, toCol, pieceInfo.piece, promote || false);
-        
-        // 成り処理後の盤面状態を確認
-        if (promote) {
-          console.log('=== 成り処理後の盤面状態確認 ===');
-          const movedPieceInfo = newBoard.get_piece_by_coords(toRow, toCol);
-          console.log('移動先の駒:', movedPieceInfo);
-          console.log('移動先の駒の種類:', movedPieceInfo.piece);
-          console.log('WASMの成り駒値:', {
-            PromotedPawn: (window as any).wasmModule?.Piece?.PromotedPawn,
-            PromotedLance: (window as any).wasmModule?.Piece?.PromotedLance,
-            PromotedKnight: (window as any).wasmModule?.Piece?.PromotedKnight,
-            PromotedSilver: (window as any).wasmModule?.Piece?.PromotedSilver,
-            PromotedBishop: (window as any).wasmModule?.Piece?.PromotedBishop,
-            PromotedRook: (window as any).wasmModule?.Piece?.PromotedRook,
-          });
-          
-          // 盤面全体の状態も確認
-          const boardState = newBoard.debug_board_state();
-          console.log('盤面全体の状態:', boardState);
+        recordMove(selectedRow, selectedCol, toRow, toCol, outcome.get_piece(), outcome.is_promotion());
+
+        const gameEndMessage = getGameEndMessage(outcome);
+        if (gameEndMessage) {
+          setTimeout(() => alert(gameEndMessage), 0);
         }
       } catch (err) {
         console.error('移動の実行中にエラー:', err);
@@ -674,20 +796,25 @@ export const ShogiBoard: React.FC = () => {
           }
           
           // 盤面の状態も表示
-          const boardState = newBoard.debug_board_state();
-          console.log('盤面の状態:', boardState);
+          console.log('盤面の状態:\n' + newBoard.to_bod());
           
           // 持ち駒の状態も表示
           const capturedPiecesState = newBoard.debug_captured_pieces();
           console.log('持ち駒の状態:', capturedPiecesState);
           
-          if (newBoard.drop_piece(pieceEnum, row, col)) {
-            console.log('持ち駒ドロップ成功');
+          const outcome = newBoard.drop_piece_with_outcome(pieceEnum, row, col);
+          if (outcome) {
+            console.log('持ち駒ドロップ成功:', outcome.get_usi(), outcome.is_check() ? '（王手）' : '');
             
 
             
             // 持ち駒ドロップを記録（記録関数内で指し手を実行するため、ここでは記録のみ）
             recordMove(-1, -1, row, col, pieceEnum, false);
+
+            const gameEndMessage = getGameEndMessage(outcome);
+            if (gameEndMessage) {
+              setTimeout(() => alert(gameEndMessage), 0);
+            }
             
             setBoard(newBoard);
             setSelectedCapturedPiece(null);
@@ -819,7 +946,77 @@ export const ShogiBoard: React.FC = () => {
   }, [selectedCapturedPiece, selectedCapturedPiecePlayer, board]);
 
   // 局面編集用のハンドラー
+  const validationMessages = useMemo(
+    () => (isEditMode ? getValidationMessages(board) : []),
+    [board, isEditMode, uiVersion]
+  );
+
+  // 対局時計の開始・一時停止・再開と、時間切れの確認
+  const handleStartClock = useCallback((settings: ClockSettings) => {
+    if (!board) return;
+    const wasm = window.wasmModule;
+    const clock = new wasm.Clock(
+      settings.mainMinutes * 60000,
+      settings.byoyomiSeconds * 1000,
+      settings.periods,
+      settings.incrementSeconds * 1000
+    );
+    const now = Date.now();
+    clock.start(now, board.get_current_player());
+    clockRef.current = clock;
+    setClockNow(now);
+  }, [board]);
+
+  const handlePauseClock = useCallback(() => {
+    clockRef.current?.pause(Date.now());
+    setClockNow(Date.now());
+  }, []);
+
+  const handleResumeClock = useCallback(() => {
+    clockRef.current?.resume(Date.now());
+    setClockNow(Date.now());
+  }, []);
+
+  useEffect(() => {
+    const timer = setInterval(() => {
+      const clock = clockRef.current;
+      if (!clock || !clock.is_running()) return;
+      const now = Date.now();
+      setClockNow(now);
+      const loser = clock.check_time(now);
+      if (loser !== undefined) {
+        announceTimeLoss(loser);
+        setGameResult(new window.wasmModule.GameResult(window.wasmModule.Termination.TimeUp, currentMoveIndex + 1, loser));
+      }
+    }, 200);
+    return () => clearInterval(timer);
+  }, [currentMoveIndex]);
+
+  // トライルールは対局の設定なので、盤面を作り直しても引き継ぐ
+  useEffect(() => {
+    if (board && board.is_try_rule() !== tryRule) {
+      board.set_try_rule(tryRule);
+    }
+  }, [board, tryRule]);
+
+  const hangingWarnings = useMemo(
+    () => (!isEditMode && currentMoveIndex >= 0 ? getHangingWarnings(board) : []),
+    [board, isEditMode, currentMoveIndex]
+  );
+
+  const castleLabels = useMemo(() => getCastleLabels(board), [board, uiVersion]);
+
   const handleEditModeToggle = useCallback(() => {
+    // 編集を終えて対局に戻る前に局面の問題点を確認する
+    if (isEditMode) {
+      const errors = getValidationMessages(board).filter(issue => issue.isError);
+      if (errors.length > 0) {
+        const list = errors.map(issue => `・${issue.message}`).join('\n');
+        if (!window.confirm(`局面に問題があります。\n${list}\n\nこのまま編集を終了しますか？`)) {
+          return;
+        }
+      }
+    }
     setIsEditMode(!isEditMode);
     setSelectedPosition(null);
     setValidMoves([]);
@@ -828,7 +1025,7 @@ export const ShogiBoard: React.FC = () => {
     setIsDroppingMode(false);
     setSelectedEditPiece(null);
     setSelectedEditPlayer(null);
-  }, [isEditMode]);
+  }, [isEditMode, board]);
 
   const handleResetToInitial = useCallback(() => {
     if (!board) return;
@@ -857,6 +1054,30 @@ export const ShogiBoard: React.FC = () => {
     console.log('平手初期状態にリセットしました');
   }, [board]);
 
+  const handleResetToHandicap = useCallback((kind: number) => {
+    if (!board) return;
+    const newBoard = window.wasmModule.Board.from_handicap(kind);
+    setIsBoardFlipped(false);
+    setBoard(newBoard);
+    setMoveHistory([]); // 棋譜履歴をリセット
+    setCurrentMoveIndex(-1); // 現在の指し手インデックスをリセット
+    setPieceStates({});
+    // 選択/編集/ドラッグ/ドロップ状態をクリア
+    setSelectedCapturedPiece(null);
+    setSelectedCapturedPiecePlayer(null);
+    setIsDroppingMode(false);
+    setSelectedEditPiece(null);
+    setSelectedEditPlayer(null);
+    setDraggedPiece(null);
+    setDraggedPlayer(null);
+    setDragStartPosition(null);
+    setSelectedPosition(null);
+    setValidMoves([]);
+    setUiVersion(v => v + 1);
+
+    console.log(`${window.wasmModule.handicap_kif_name(kind)}の初期状態にしました`);
+  }, [board]);
+
   const handleResetToMatePosition = useCallback(() => {
     if (!board) return;
     const newBoard = board.clone();
@@ -868,24 +1089,10 @@ export const ShogiBoard: React.FC = () => {
     newBoard.clear_captured_pieces(window.wasmModule.Player.White);
     
     // 後手玉を初期位置に配置
-    newBoard.set_piece_by_coords(8, 4, window.wasmModule.Piece.King, window.wasmModule.Player.White);
-    
-    // 玉以外の駒の枚数を倍にして、先手と後手の両方に持ち駒を設定
-    const pieces = [
-      { piece: window.wasmModule.Piece.Pawn, count: 18 }, // 9 * 2
-      { piece: window.wasmModule.Piece.Lance, count: 4 }, // 2 * 2
-      { piece: window.wasmModule.Piece.Knight, count: 4 }, // 2 * 2
-      { piece: window.wasmModule.Piece.Silver, count: 4 }, // 2 * 2
-      { piece: window.wasmModule.Piece.Gold, count: 4 }, // 2 * 2
-      { piece: window.wasmModule.Piece.Bishop, count: 2 }, // 1 * 2
-      { piece: window.wasmModule.Piece.Rook, count: 2 }, // 1 * 2
-      { piece: window.wasmModule.Piece.King, count: 1 } // 玉は1枚のみ
-    ];
+    newBoard.edit_place_from_box(8, 4, window.wasmModule.Piece.King, window.wasmModule.Player.White);
     
-    // 後手のみに持ち駒を設定（先手は持ち駒なし）
-    for (const { piece, count } of pieces) {
-      newBoard.set_captured_piece_count(window.wasmModule.Player.White, piece, count);
-    }
+    // 残りの駒（先手玉以外）はすべて後手の持ち駒にする
+    newBoard.edit_send_box_to_hand(window.wasmModule.Player.White);
     
     // 手番を先手に設定
     newBoard.set_current_player(window.wasmModule.Player.Black);
@@ -937,16 +1144,10 @@ export const ShogiBoard: React.FC = () => {
 
   // 駒の状態を次の状態に変更する関数
   const cyclePieceState = useCallback((row: number, col: number) => {
-    const key = `${row}-${col}`;
-    setPieceStates(prev => {
-      const currentState = prev[key] || 0;
-      const nextState = (currentState + 1) % 4;
-      return {
-        ...prev,
-        [key]: nextState
-      };
-    });
-  }, []);
+    const newBoard = board.clone();
+    newBoard.edit_cycle_piece(row, col);
+    setBoard(newBoard);
+  }, [board]);
 
   const handleEditSquareClick = useCallback((row: number, col: number) => {
     if (!board || !isEditMode) return;
@@ -958,15 +1159,12 @@ export const ShogiBoard: React.FC = () => {
       return;
     }
     
+    // 駒箱に残っている駒だけを置ける
     if (selectedEditPiece) {
       const newBoard = board.clone();
-      newBoard.set_piece_by_coords(row, col, selectedEditPiece, selectedEditPlayer);
-      setBoard(newBoard);
-    } else {
-      // 駒が選択されていない場合は削除
-      const newBoard = board.clone();
-      newBoard.clear_square_by_coords(row, col);
-      setBoard(newBoard);
+      if (newBoard.edit_place_from_box(row, col, selectedEditPiece, selectedEditPlayer)) {
+        setBoard(newBoard);
+      }
     }
   }, [board, isEditMode, selectedEditPiece, selectedEditPlayer, cyclePieceState]);
 
@@ -984,12 +1182,12 @@ export const ShogiBoard: React.FC = () => {
         window.wasmModule.Piece.Silver,
         window.wasmModule.Piece.Gold,
         window.wasmModule.Piece.Bishop,
-        window.wasmModule.Piece.Rook,
-        window.wasmModule.Piece.King
+        window.wasmModule.Piece.Rook
       ];
       setDraggedPiece(pieceTypes[piece.pieceType]);
       setDraggedPlayer(player);
       setDragStartPosition(null); // 持ち駒からドラッグの場合は位置なし
+      setIsDraggingFromBox(false);
     } else {
       // 盤面の駒からドラッグ
       setDraggedPiece(piece);
@@ -1002,10 +1200,11 @@ export const ShogiBoard: React.FC = () => {
         const row = parseInt(squareElement.getAttribute('data-row') || '0');
         const col = parseInt(squareElement.getAttribute('data-col') || '0');
         setDragStartPosition({ row, col });
+        setIsDraggingFromBox(false);
         
-        // 元の位置の駒を削除
+        // ドラッグ中は元の位置の駒を駒箱に入れておく
         const newBoard = board.clone();
-        newBoard.clear_square_by_coords(row, col);
+        newBoard.edit_board_to_box(row, col);
         setBoard(newBoard);
       }
     }
@@ -1013,53 +1212,39 @@ export const ShogiBoard: React.FC = () => {
     event.dataTransfer.effectAllowed = 'move';
   }, [isEditMode, board]);
 
+  const handleBoxPieceDragStart = useCallback((piece: any, event: React.DragEvent) => {
+    if (!isEditMode) return;
+    setDraggedPiece(piece);
+    setDraggedPlayer(window.wasmModule.Player.Black);
+    setDragStartPosition(null);
+    setIsDraggingFromBox(true);
+    event.dataTransfer.effectAllowed = 'move';
+  }, [isEditMode]);
+
+  // 盤上・駒箱からのドラッグは駒箱の駒を、それ以外は持ち駒を動かす
+  const currentEditDrag = useCallback((): EditDrag => ({
+    piece: draggedPiece,
+    player: draggedPlayer,
+    fromHand: !dragStartPosition && !isDraggingFromBox,
+  }), [draggedPiece, draggedPlayer, dragStartPosition, isDraggingFromBox]);
+
+  const finishEditDrag = useCallback(() => {
+    setDraggedPiece(null);
+    setDraggedPlayer(null);
+    setDragStartPosition(null);
+    setIsDraggingFromBox(false);
+  }, []);
+
   const handleSquareDrop = useCallback((row: number, col: number, event: React.DragEvent) => {
     if (!board || !isEditMode || !draggedPiece) return;
     
     event.preventDefault();
     const newBoard = board.clone();
-    
-    // 移動先に駒があるかチェック
-    const existingPieceInfo = newBoard.get_piece_by_coords(row, col);
-    const existingPiece = existingPieceInfo.piece;
-    
-    // 移動先に駒がある場合、その駒を持ち駒に追加
-    if (existingPiece !== (window as any).wasmModule?.Piece?.Empty) {
-      // 駒を成り駒から元の駒に戻す
-      let pieceToAdd = existingPiece;
-      const wasm = (window as any).wasmModule;
-      
-      // 成り駒の場合は元の駒に戻す
-      if (existingPiece === wasm.Piece.PromotedPawn) {
-        pieceToAdd = wasm.Piece.Pawn;
-      } else if (existingPiece === wasm.Piece.PromotedLance) {
-        pieceToAdd = wasm.Piece.Lance;
-      } else if (existingPiece === wasm.Piece.PromotedKnight) {
-        pieceToAdd = wasm.Piece.Knight;
-      } else if (existingPiece === wasm.Piece.PromotedSilver) {
-        pieceToAdd = wasm.Piece.Silver;
-      } else if (existingPiece === wasm.Piece.PromotedBishop) {
-        pieceToAdd = wasm.Piece.Bishop;
-      } else if (existingPiece === wasm.Piece.PromotedRook) {
-        pieceToAdd = wasm.Piece.Rook;
-      }
-      
-      // 取った駒を相手の持ち駒に追加
-      newBoard.add_captured_piece(draggedPlayer, pieceToAdd);
-    }
-    
-    // 持ち駒からのドロップであれば、持ち駒を1枚消費
-    if (!dragStartPosition) {
-      newBoard.use_captured_piece(draggedPlayer, draggedPiece);
-    }
-    
-    // 新しい駒を配置
-    newBoard.set_piece_by_coords(row, col, draggedPiece, draggedPlayer);
+    // 移動先に駒がある場合は、その駒を置く側の持ち駒にする
+    dropOnSquare(newBoard, currentEditDrag(), row, col);
     setBoard(newBoard);
-    setDraggedPiece(null);
-    setDraggedPlayer(null);
-    setDragStartPosition(null);
-  }, [board, isEditMode, draggedPiece, draggedPlayer, dragStartPosition]);
+    finishEditDrag();
+  }, [board, isEditMode, draggedPiece, currentEditDrag, finishEditDrag]);
 
   const handleSquareDragOver = useCallback((event: React.DragEvent) => {
     if (isEditMode) {
@@ -1073,36 +1258,40 @@ export const ShogiBoard: React.FC = () => {
     
     event.preventDefault();
     const newBoard = board.clone();
-    
-    // 駒台→駒台 の場合は、元の持ち駒を1枚消費してから、
-    // ドロップ先プレイヤーの持ち駒に1枚追加する
-    if (!dragStartPosition && draggedPlayer !== undefined && draggedPlayer !== null) {
-      newBoard.use_captured_piece(draggedPlayer, draggedPiece);
-    }
-    
-    // ドラッグされた駒を指定プレイヤーの持ち駒に追加
-    newBoard.add_captured_piece(player, draggedPiece);
-    
+    dropOnHand(newBoard, currentEditDrag(), player);
     setBoard(newBoard);
-    setDraggedPiece(null);
-    setDraggedPlayer(null);
-    setDragStartPosition(null);
-  }, [board, isEditMode, draggedPiece, draggedPlayer, dragStartPosition]);
+    finishEditDrag();
+  }, [board, isEditMode, draggedPiece, currentEditDrag, finishEditDrag]);
+
+  const handlePieceBoxDrop = useCallback((event: React.DragEvent) => {
+    if (!board || !isEditMode || !draggedPiece) return;
+
+    event.preventDefault();
+    const newBoard = board.clone();
+    dropOnBox(newBoard, currentEditDrag());
+    setBoard(newBoard);
+    finishEditDrag();
+  }, [board, isEditMode, draggedPiece, currentEditDrag, finishEditDrag]);
+
+  const handleSendBoxToHand = useCallback((player: any) => {
+    if (!board) return;
+    const newBoard = board.clone();
+    newBoard.edit_send_box_to_hand(player);
+    setBoard(newBoard);
+  }, [board]);
 
   const handleDragEnd = useCallback((event: React.DragEvent) => {
     if (!isEditMode || !draggedPiece || !dragStartPosition) return;
     
-    // ドラッグがキャンセルされた場合、元の位置に駒を戻す
+    // ドラッグがキャンセルされた場合、駒箱に入れておいた駒を元の位置に戻す
     if (event.dataTransfer.dropEffect === 'none') {
       const newBoard = board.clone();
-      newBoard.set_piece_by_coords(dragStartPosition.row, dragStartPosition.col, draggedPiece, draggedPlayer);
+      newBoard.edit_place_from_box(dragStartPosition.row, dragStartPosition.col, draggedPiece, draggedPlayer);
       setBoard(newBoard);
     }
     
-    setDraggedPiece(null);
-    setDraggedPlayer(null);
-    setDragStartPosition(null);
-  }, [isEditMode, draggedPiece, draggedPlayer, dragStartPosition, board]);
+    finishEditDrag();
+  }, [isEditMode, draggedPiece, draggedPlayer, dragStartPosition, board, finishEditDrag]);
 
   const handleDragLeave = useCallback((event: React.DragEvent) => {
     if (isEditMode) {
@@ -1116,19 +1305,7 @@ export const ShogiBoard: React.FC = () => {
   }, [isEditMode]);
 
   // タッチ操作（スマホ）用ユーティリティ
-  const demoteIfPromoted = useCallback((piece: any) => {
-    const wasm = (window as any).wasmModule;
-    if (!wasm) return piece;
-    if (piece === wasm.Piece.PromotedPawn) return wasm.Piece.Pawn;
-    if (piece === wasm.Piece.PromotedLance) return wasm.Piece.Lance;
-    if (piece === wasm.Piece.PromotedKnight) return wasm.Piece.Knight;
-    if (piece === wasm.Piece.PromotedSilver) return wasm.Piece.Silver;
-    if (piece === wasm.Piece.PromotedBishop) return wasm.Piece.Bishop;
-    if (piece === wasm.Piece.PromotedRook) return wasm.Piece.Rook;
-    return piece;
-  }, []);
-
-  const getDropTargetFromPoint = useCallback((clientX: number, clientY: number): { type: 'board'; row: number; col: number } | { type: 'captured'; player: any } | null => {
+  const getDropTargetFromPoint = useCallback((clientX: number, clientY: number): { type: 'board'; row: number; col: number } | { type: 'captured'; player: any } | { type: 'box' } | null => {
     const elements = (document as any).elementsFromPoint ? (document as any).elementsFromPoint(clientX, clientY) : [document.elementFromPoint(clientX, clientY)];
     for (const el of elements) {
       if (!el) continue;
@@ -1139,6 +1316,10 @@ export const ShogiBoard: React.FC = () => {
         const col = parseInt(square.getAttribute('data-col') || '0');
         return { type: 'board', row, col };
       }
+      // 駒箱上（持ち駒リストと同じクラスを持つので先に調べる）
+      if ((el as HTMLElement).closest?.('.piece-box-list')) {
+        return { type: 'box' };
+      }
       // 持ち駒リスト上
       const capturedList = (el as HTMLElement).closest?.('.captured-pieces-list') as HTMLElement | null;
       if (capturedList && capturedList.hasAttribute('data-player')) {
@@ -1162,9 +1343,10 @@ export const ShogiBoard: React.FC = () => {
     setDraggedPiece(piece);
     setDraggedPlayer(player);
     setDragStartPosition({ row, col });
-    // 元の位置の駒を削除
+    setIsDraggingFromBox(false);
+    // ドラッグ中は元の位置の駒を駒箱に入れておく
     const newBoard = board.clone();
-    newBoard.clear_square_by_coords(row, col);
+    newBoard.edit_board_to_box(row, col);
     setBoard(newBoard);
   }, [isEditMode, board]);
 
@@ -1188,6 +1370,18 @@ export const ShogiBoard: React.FC = () => {
     setDraggedPiece(wasmPiece);
     setDraggedPlayer(player);
     setDragStartPosition(null); // 持ち駒から
+    setIsDraggingFromBox(false);
+  }, [isEditMode]);
+
+  // タッチ: 駒箱から開始
+  const handleBoxPieceTouchStart = useCallback((piece: any, event: React.TouchEvent) => {
+    if (!isEditMode) return;
+    event.preventDefault();
+    touchDragRef.current = { active: true };
+    setDraggedPiece(piece);
+    setDraggedPlayer(window.wasmModule.Player.Black);
+    setDragStartPosition(null);
+    setIsDraggingFromBox(true);
   }, [isEditMode]);
 
   // タッチ移動: 今回は視覚的ゴーストは作らず、指を追従するのみ
@@ -1203,57 +1397,23 @@ export const ShogiBoard: React.FC = () => {
     const touch = event.changedTouches[0];
     const target = getDropTargetFromPoint(touch.clientX, touch.clientY);
     const newBoard = board.clone();
+    const drag = currentEditDrag();
 
     if (target && target.type === 'board') {
-      const { row, col } = target;
-      // 既存駒の取り扱い
-      const existingPieceInfo = newBoard.get_piece_by_coords(row, col);
-      const existingPiece = existingPieceInfo.piece;
-      if (existingPiece !== (window as any).wasmModule?.Piece?.Empty) {
-        let pieceToAdd = demoteIfPromoted(existingPiece);
-        newBoard.add_captured_piece(draggedPlayer, pieceToAdd);
-      }
-      // 持ち駒からのドロップなら消費
-      if (!dragStartPosition) {
-        newBoard.use_captured_piece(draggedPlayer, draggedPiece);
-      }
-      newBoard.set_piece_by_coords(row, col, draggedPiece, draggedPlayer);
-      setBoard(newBoard);
-      setDraggedPiece(null);
-      setDraggedPlayer(null);
-      setDragStartPosition(null);
-      touchDragRef.current = null;
-      return;
-    }
-
-    if (target && target.type === 'captured') {
-      const targetPlayer = target.player;
-      // 盤→駒台: 成りは元に戻す
-      const pieceToAdd = demoteIfPromoted(draggedPiece);
-      // 駒台→駒台なら元の持ち駒を消費
-      if (!dragStartPosition && draggedPlayer !== undefined && draggedPlayer !== null) {
-        newBoard.use_captured_piece(draggedPlayer, draggedPiece);
-      }
-      newBoard.add_captured_piece(targetPlayer, pieceToAdd);
-      setBoard(newBoard);
-      setDraggedPiece(null);
-      setDraggedPlayer(null);
-      setDragStartPosition(null);
-      touchDragRef.current = null;
-      return;
-    }
-
-    // どこにもドロップされなかった: 元に戻す（盤から開始した場合のみ）
-    if (dragStartPosition) {
+      dropOnSquare(newBoard, drag, target.row, target.col);
+    } else if (target && target.type === 'captured') {
+      dropOnHand(newBoard, drag, target.player);
+    } else if (target && target.type === 'box') {
+      dropOnBox(newBoard, drag);
+    } else if (dragStartPosition) {
+      // どこにもドロップされなかった: 元に戻す（盤から開始した場合のみ）
       const { row, col } = dragStartPosition;
-      newBoard.set_piece_by_coords(row, col, draggedPiece, draggedPlayer);
-      setBoard(newBoard);
+      newBoard.edit_place_from_box(row, col, draggedPiece, draggedPlayer);
     }
-    setDraggedPiece(null);
-    setDraggedPlayer(null);
-    setDragStartPosition(null);
+    setBoard(newBoard);
+    finishEditDrag();
     touchDragRef.current = null;
-  }, [isEditMode, draggedPiece, draggedPlayer, dragStartPosition, board, getDropTargetFromPoint, demoteIfPromoted]);
+  }, [isEditMode, draggedPiece, draggedPlayer, dragStartPosition, board, getDropTargetFromPoint, currentEditDrag, finishEditDrag]);
 
   // 同じ駒が複数ある場合の区別表記を生成する関数
   const generateDisambiguation = useCallback((fromRow: number, fromCol: number, toRow: number, toCol: number, piece: any, player: any) => {
@@ -1423,6 +1583,14 @@ export const ShogiBoard: React.FC = () => {
       newBoard.make_move_by_coords_with_promotion(fromRow, fromCol, toRow, toCol, isPromoted);
     }
     
+    // 対局時計が動いていれば時計を押し、この手の消費時間を記録する
+    const clock = clockRef.current;
+    const consumedMs = clock && clock.is_running() ? clock.press(Date.now()) : undefined;
+    const timeLoser = consumedMs !== undefined ? clock.get_loser() : undefined;
+    if (timeLoser !== undefined) {
+      announceTimeLoss(timeLoser);
+    }
+
     const newMove = {
       moveNumber,
       notation,
@@ -1433,12 +1601,24 @@ export const ShogiBoard: React.FC = () => {
       toRow,
       toCol,
       piece, // 駒の種類を追加
-      isPromoted // 成り情報を追加
+      isPromoted, // 成り情報を追加
+      consumedMs
     };
 
     // 現在の指し手インデックス以降の履歴を削除（新しい指し手を追加する場合）
     const updatedHistory = moveHistory.slice(0, currentMoveIndex + 1);
     updatedHistory.push(newMove);
+
+    // この手で対局が終わっていれば棋譜に終局を記録する（続く場合は以前の終局を消す）
+    const wasm = window.wasmModule;
+    const termination = newBoard.termination();
+    if (timeLoser !== undefined) {
+      setGameResult(new wasm.GameResult(wasm.Termination.TimeUp, updatedHistory.length, timeLoser));
+    } else if (termination !== undefined) {
+      setGameResult(new wasm.GameResult(termination, updatedHistory.length, newBoard.get_current_player()));
+    } else {
+      setGameResult(null);
+    }
     
     setMoveHistory(updatedHistory);
     setCurrentMoveIndex(updatedHistory.length - 1);
@@ -1568,6 +1748,7 @@ export const ShogiBoard: React.FC = () => {
 
     // 現在の局面までの手数をコピー（currentMoveIndex + 1まで）
     const movesToCopy = currentMoveIndex === -1 ? 0 : currentMoveIndex + 1;
+    const totalConsumed = [0, 0]; // 手数の偶奇ごと（先に指す側・後に指す側）の消費時間の合計。駒落ちでは先に指すのが後手
     
     for (let index = 0; index < movesToCopy; index++) {
       const move = moveHistory[index];
@@ -1611,16 +1792,24 @@ export const ShogiBoard: React.FC = () => {
         );
       }
       
-      const timeInfo = '0:0/0:0:0';
+      const consumedMs = move.consumedMs ?? 0;
+      totalConsumed[index % 2] += consumedMs;
+      const timeInfo = window.wasmModule.format_kif_time(consumedMs, totalConsumed[index % 2]);
       
       // 手数のフォーマットを修正（奇数手目にもスペースを追加）
       const spacing = isFirstPlayer ? '   ' : ''; // 奇数手目に3つのスペースを追加
-      kifContent += `${moveNumber.toString().padStart(3)}${spacing}${playerPrefix}${moveNotation}   (${timeInfo})\r\n`;
+      kifContent += `${moveNumber.toString().padStart(3)}${spacing}${playerPrefix}${moveNotation}   ${timeInfo}\r\n`;
     }
 
-    kifContent += 'まで' + movesToCopy + '手で終了\r\n';
+    // 終局が記録されていて、その局面まで書き出す場合は終局の手と結果を書く
+    if (gameResult && gameResult.get_plies() === movesToCopy) {
+      kifContent += `${(movesToCopy + 1).toString().padStart(3)} ${gameResult.get_kif_move_name()}\r\n`;
+      kifContent += `${gameResult.get_kif_summary()}\r\n`;
+    } else {
+      kifContent += 'まで' + movesToCopy + '手で終了\r\n';
+    }
     return kifContent;
-  }, [moveHistory, currentMoveIndex, convertToKifNotation]);
+  }, [moveHistory, currentMoveIndex, convertToKifNotation, gameResult]);
 
   // KIFファイルをクリップボードにコピーする関数
   const handleCopyKif = useCallback(async () => {
@@ -1647,6 +1836,137 @@ export const ShogiBoard: React.FC = () => {
     }
   }, [generateKifContent]);
 
+  // 局面図（柿木形式のテキスト）をクリップボードにコピーする関数
+  const handleCopyBoardText = useCallback(async () => {
+    if (!board) return;
+    try {
+      await navigator.clipboard.writeText(board.to_bod());
+      alert('局面図をクリップボードにコピーしました。');
+    } catch (error) {
+      console.error('Failed to copy: ', error);
+      alert('クリップボードへのコピーに失敗しました。');
+    }
+  }, [board]);
+
+  // 局面図をSVGファイルとして保存する関数（直前の指し手を強調し、盤の向きも反映する）
+  const handleDownloadBoardSvg = useCallback(() => {
+    if (!board) return;
+    const wasm = window.wasmModule;
+    const options = new wasm.SvgOptions();
+    options.set_flipped(isBoardFlipped);
+    const lastMove = currentMoveIndex >= 0 ? moveHistory[currentMoveIndex] : null;
+    if (lastMove && lastMove.toRow !== undefined && lastMove.toCol !== undefined) {
+      const to = new wasm.Position(lastMove.toRow, lastMove.toCol);
+      const move = lastMove.fromRow === -1 || lastMove.fromRow === undefined
+        ? wasm.Move.new_drop(lastMove.piece, to)
+        : new wasm.Move(new wasm.Position(lastMove.fromRow, lastMove.fromCol ?? 0), to, lastMove.piece, !!lastMove.isPromoted);
+      options.set_last_move(move);
+    }
+
+    const svg = board.to_svg(options);
+    const url = URL.createObjectURL(new Blob([svg], { type: 'image/svg+xml' }));
+    const link = document.createElement('a');
+    link.href = url;
+    link.download = 'shogi-board.svg';
+    link.click();
+    URL.revokeObjectURL(url);
+  }, [board, isBoardFlipped, moveHistory, currentMoveIndex]);
+
+  // キーボードで入力した指し手（"76歩"、"同銀"、"58金右" など）を指す関数
+  const handleMoveTextSubmit = useCallback((event: React.FormEvent) => {
+    event.preventDefault();
+    if (!board || isEditMode || !moveText.trim()) return;
+    const wasm = window.wasmModule;
+    const lastMove = currentMoveIndex >= 0 ? moveHistory[currentMoveIndex] : null;
+    const previousTo = lastMove && lastMove.toRow !== undefined && lastMove.toCol !== undefined
+      ? new wasm.Position(lastMove.toRow, lastMove.toCol)
+      : undefined;
+
+    const result = board.parse_move_text(moveText, previousTo);
+    const error = result.get_error();
+    if (error) {
+      alert(error);
+      return;
+    }
+    const move = result.get_move();
+    if (!move) {
+      alert(`候補が複数あります: ${result.get_candidate_names().join('、')}`);
+      return;
+    }
+
+    const from = move.get_from();
+    const to = move.get_to();
+    const newBoard = board.clone();
+    if (move.is_drop()) {
+      newBoard.drop_piece(move.get_piece(), to.get_row(), to.get_column());
+      recordMove(-1, -1, to.get_row(), to.get_column(), move.get_piece(), false);
+    } else {
+      newBoard.make_move_by_coords_with_promotion(from.get_row(), from.get_column(), to.get_row(), to.get_column(), move.is_promotion());
+      recordMove(from.get_row(), from.get_column(), to.get_row(), to.get_column(), move.get_piece(), move.is_promotion());
+    }
+    setBoard(newBoard);
+    setSelectedPosition(null);
+    setSelectedCapturedPiece(null);
+    setSelectedCapturedPiecePlayer(null);
+    setIsDroppingMode(false);
+    setValidMoves([]);
+    setMoveText('');
+  }, [board, isEditMode, moveText, moveHistory, currentMoveIndex, recordMove]);
+
+  // 手番の側の入玉宣言を判定する関数
+  const handleDeclareWin = useCallback(() => {
+    if (!board || isEditMode) return;
+    const wasm = window.wasmModule;
+    const player = board.get_current_player();
+    const playerName = player === wasm.Player.Black ? '先手' : '後手';
+    const points = board.declaration_points(player);
+    // 対局時計を使っていれば、持ち時間が残っていることも宣言の条件になる
+    const rule = wasm.DeclarationRule[declarationRule];
+    const clock = clockRef.current;
+    const result = clock ? board.declare_win_on_clock(rule, clock, Date.now()) : board.declare_win(rule);
+    const termination = result === wasm.DeclarationResult.Win
+      ? wasm.Termination.EnteringKing
+      : result === wasm.DeclarationResult.Draw ? wasm.Termination.Impasse : wasm.Termination.IllegalLoss;
+    setGameResult(new wasm.GameResult(termination, currentMoveIndex + 1, player));
+    if (result === wasm.DeclarationResult.Win) {
+      alert(`${playerName}の入玉宣言が成立しました（${points}点）。${playerName}の勝ちです。`);
+    } else if (result === wasm.DeclarationResult.Draw) {
+      alert(`${playerName}の入玉宣言は${points}点のため持将棋です。`);
+    } else {
+      alert(`${playerName}の入玉宣言は条件を満たしていません（${points}点）。宣言した側の負けになります。`);
+    }
+  }, [board, isEditMode, declarationRule, currentMoveIndex]);
+
+  // 投了・千日手などの盤上では判定しない終局を、手番の側で棋譜に記録する関数
+  const handleRecordTermination = useCallback((name: string) => {
+    if (!board || name === '') return;
+    const wasm = window.wasmModule;
+    const result = new wasm.GameResult(wasm.Termination[name], currentMoveIndex + 1, board.get_current_player());
+    setGameResult(result);
+    alert(result.get_kif_summary());
+  }, [board, currentMoveIndex]);
+
+  // クリップボードの局面図（柿木形式のテキスト）を盤面に反映する関数
+  const handlePasteBoardText = useCallback(async () => {
+    try {
+      const text = await navigator.clipboard.readText();
+      const newBoard = window.wasmModule.Board.from_bod(text);
+      setIsBoardFlipped(false);
+      setBoard(newBoard);
+      setMoveHistory([]);
+      setCurrentMoveIndex(-1);
+      setSelectedCapturedPiece(null);
+      setSelectedCapturedPiecePlayer(null);
+      setIsDroppingMode(false);
+      setSelectedPosition(null);
+      setValidMoves([]);
+      setUiVersion((value) => value + 1);
+    } catch (error) {
+      const message = typeof error === 'string' ? error : '局面図の読み込みに失敗しました。';
+      alert(message);
+    }
+  }, []);
+
   // 将棋盤の画像をクリップボードにコピーする関数（盤面と持ち駒のみ）
   const handleCopyBoardImage = useCallback(async () => {
     try {
@@ -1926,7 +2246,20 @@ export const ShogiBoard: React.FC = () => {
     if (!board) return;
 
     try {
-      const newBoard = applyRecognizedPosition(board, position);
+      let newBoard = applyRecognizedPosition(position);
+
+      // 読み違いで成立しない局面になっている場合は修正候補を提示する
+      const hasErrors = getValidationMessages(newBoard).some(issue => issue.isError);
+      if (hasErrors && typeof newBoard.suggest_corrections === 'function') {
+        const [best] = newBoard.suggest_corrections(new Float64Array(0), 1);
+        if (best) {
+          const list = best.get_corrections().map((correction: any) => `・${correction.get_description()}`).join('\n');
+          if (window.confirm(`読み取った局面に矛盾があります。次のように修正しますか？\n${list}`)) {
+            newBoard = best.get_board();
+          }
+        }
+      }
+
       setIsBoardFlipped(false);
       setBoard(newBoard);
       setMoveHistory([]);
@@ -1943,7 +2276,11 @@ export const ShogiBoard: React.FC = () => {
       setSelectedPosition(null);
       setValidMoves([]);
       setUiVersion((value) => value + 1);
-      alert('写真から読み取った局面を盤面に反映しました。必要に応じて局面編集モードで修正してください。');
+      const issues = getValidationMessages(newBoard);
+      const issueText = issues.length > 0
+        ? `\n\n局面の問題点:\n${issues.map(issue => `・${issue.message}`).join('\n')}`
+        : '';
+      alert(`写真から読み取った局面を盤面に反映しました。必要に応じて局面編集モードで修正してください。${issueText}`);
     } catch (error) {
       const message = error instanceof Error ? error.message : '盤面への反映に失敗しました';
       alert(message);
@@ -1986,6 +2323,20 @@ export const ShogiBoard: React.FC = () => {
             <button onClick={handleResetToMatePosition} className="edit-control-button">
               詰め将棋初期状態にする
             </button>
+            <select
+              className="edit-control-button"
+              value=""
+              onChange={(e) => {
+                if (e.target.value !== '') handleResetToHandicap(Number(e.target.value));
+              }}
+            >
+              <option value="">駒落ち初期状態にする</option>
+              {HANDICAP_OPTIONS.map(kind => (
+                <option key={kind} value={kind}>
+                  {window.wasmModule?.handicap_kif_name(kind)}
+                </option>
+              ))}
+            </select>
             <button onClick={handleChangeTurn} className="edit-control-button">
               手番変更
             </button>
@@ -1995,6 +2346,47 @@ export const ShogiBoard: React.FC = () => {
           </div>
         )}
 
+        {/* 局面編集中の駒箱 */}
+        {isEditMode && board && (
+          <PieceBox
+            board={board}
+            onDragStart={handleBoxPieceDragStart}
+            onDrop={handlePieceBoxDrop}
+            onDragOver={handleSquareDragOver}
+            onTouchStart={handleBoxPieceTouchStart}
+            onSendToHand={handleSendBoxToHand}
+          />
+        )}
+
+        {/* 局面編集中の問題点 */}
+        {isEditMode && validationMessages.length > 0 && (
+          <ul className="validation-issues">
+            {validationMessages.map((issue, index) => (
+              <li key={index} className={issue.isError ? 'validation-error' : 'validation-warning'}>
+                {issue.message}
+              </li>
+            ))}
+          </ul>
+        )}
+
+        {/* 直前に指した側の浮き駒 */}
+        {hangingWarnings.length > 0 && (
+          <ul className="validation-issues">
+            {hangingWarnings.map((message, index) => (
+              <li key={index} className="validation-warning">
+                {message}
+              </li>
+            ))}
+          </ul>
+        )}
+        {castleLabels.length > 0 && (
+          <div className="castle-labels">
+            {castleLabels.map((label, index) => (
+              <span key={index} className="castle-label">{label}</span>
+            ))}
+          </div>
+        )}
+
         <div className="board-layout" onTouchMove={handleTouchMoveGlobal} onTouchEnd={handleTouchEndGlobal}>
           {/* 後手の持ち駒（左側） */}
           {board && (
@@ -2024,7 +2416,8 @@ export const ShogiBoard: React.FC = () => {
                 {renderBoard(
                   board, selectedPosition, validMoves, handleSquareClick, isDroppingMode, isEditMode, getPieceState, isBoardFlipped,
                   handleSquareDrop, handleSquareDragOver, handlePieceDragStart, handleDragEnd, handleDragLeave,
-                  handleSquareTouchStart, handleTouchMoveGlobal, handleTouchEndGlobal
+                  handleSquareTouchStart, handleTouchMoveGlobal, handleTouchEndGlobal,
+                  showAttacks ? Array.from(board.attack_counts() as Uint8Array) : null
                 )}
               </div>
             )}
@@ -2071,6 +2464,74 @@ export const ShogiBoard: React.FC = () => {
               </div>
             </div>
 
+            <label className="attack-toggle">
+              <input
+                type="checkbox"
+                checked={showAttacks}
+                onChange={(e) => setShowAttacks(e.target.checked)}
+              />
+              利きを表示
+            </label>
+
+            {/* 対局時計 */}
+            <GameClock
+              clock={clockRef.current}
+              now={clockNow}
+              onStart={handleStartClock}
+              onPause={handlePauseClock}
+              onResume={handleResumeClock}
+            />
+
+            {/* 入玉宣言 */}
+            <div className="declaration-controls">
+              <select
+                value={declarationRule}
+                onChange={(e) => setDeclarationRule(e.target.value as 'Points24' | 'Points27')}
+              >
+                <option value="Points27">27点法</option>
+                <option value="Points24">24点法</option>
+              </select>
+              <button onClick={handleDeclareWin} className="image-copy-button" disabled={isEditMode}>
+                入玉宣言
+              </button>
+              <label className="rule-toggle">
+                <input
+                  type="checkbox"
+                  checked={tryRule}
+                  onChange={(e) => setTryRule(e.target.checked)}
+                />
+                トライルール
+              </label>
+              <select
+                value=""
+                onChange={(e) => handleRecordTermination(e.target.value)}
+                disabled={isEditMode}
+              >
+                <option value="">終局を記録</option>
+                <option value="Resign">投了</option>
+                <option value="Repetition">千日手</option>
+                <option value="Impasse">持将棋</option>
+                <option value="IllegalWin">反則勝ち</option>
+                <option value="IllegalLoss">反則負け</option>
+                <option value="Interrupt">中断</option>
+              </select>
+            </div>
+
+            {/* キーボードでの指し手入力 */}
+            <form className="move-text-form" onSubmit={handleMoveTextSubmit}>
+              <input
+                type="text"
+                className="move-text-input"
+                value={moveText}
+                onChange={(e) => setMoveText(e.target.value)}
+                placeholder="指し手を入力（例: 76歩、同銀、58金右）"
+                disabled={isEditMode}
+              />
+              <button type="submit" className="move-text-button" disabled={isEditMode || !moveText.trim()}>
+                指す
+              </button>
+            </form>
+
             {/* KIFファイルコピーボタンと画像コピーボタン */}
             <div className="copy-controls">
               <button
@@ -2092,6 +2553,24 @@ export const ShogiBoard: React.FC = () => {
               >
                 盤面画像をコピー
               </button>
+              <button 
+                onClick={handleCopyBoardText}
+                className="image-copy-button"
+              >
+                局面図をコピー
+              </button>
+              <button 
+                onClick={handlePasteBoardText}
+                className="image-copy-button"
+              >
+                局面図を貼り付け
+              </button>
+              <button 
+                onClick={handleDownloadBoardSvg}
+                className="image-copy-button"
+              >
+                局面図SVGを保存
+              </button>
             </div>
           </div>
           
@@ -29,6 +29,93 @@ declare module 'shogi_core' {
     White,
   }
 
+  export enum Handicap {
+    Even,
+    Lance,
+    RightLance,
+    Bishop,
+    Rook,
+    RookLance,
+    TwoPieces,
+    FourPieces,
+    SixPieces,
+    EightPieces,
+    TenPieces,
+  }
+
+  export enum IssueKind {
+    KingCount,
+    TooManyPieces,
+    PieceCountExceeded,
+    DoublePawn,
+    DeadPiece,
+    OpponentInCheck,
+    BothKingsInCheck,
+  }
+
+  export enum GameStatus {
+    Ongoing,
+    Checkmate,
+    NoLegalMoves,
+    KingCaptured,
+    Try,
+  }
+
+  export enum Termination {
+    Resign,
+    Checkmate,
+    Repetition,
+    Impasse,
+    IllegalWin,
+    IllegalLoss,
+    TimeUp,
+    Interrupt,
+    EnteringKing,
+    Try,
+  }
+
+  export enum DeclarationRule {
+    Points24,
+    Points27,
+  }
+
+  export enum DeclarationResult {
+    Win,
+    Draw,
+    Invalid,
+  }
+
+  export class ValidationIssue {
+    get_kind(): IssueKind;
+    is_error(): boolean;
+    get_row(): number;
+    get_column(): number;
+    get_piece(): Piece;
+    get_message(): string;
+  }
+
+  export class Correction {
+    get_row(): number;
+    get_column(): number;
+    is_hand(): boolean;
+    get_old_piece(): Piece;
+    get_old_player(): Player;
+    get_new_piece(): Piece;
+    get_new_player(): Player;
+    get_description(): string;
+  }
+
+  export class CorrectionCandidate {
+    get_corrections(): Correction[];
+    get_cost(): number;
+    get_board(): Board;
+  }
+
+  export class InferredMoves {
+    get_moves(): Move[];
+    get_mismatches(): number;
+  }
+
   export class Position {
     constructor(row: number, column: number);
     row: number;
@@ -38,13 +125,140 @@ declare module 'shogi_core' {
     get_column(): number;
   }
 
+  export class Move {
+    constructor(from: Position, to: Position, piece: Piece, promote: boolean);
+    static new_drop(piece: Piece, to: Position): Move;
+    get_from(): Position;
+    get_to(): Position;
+    get_piece(): Piece;
+    is_promotion(): boolean;
+    is_drop(): boolean;
+    to_usi(): string;
+    debug_info(): string;
+  }
+
+  export class UndoInfo {
+    get_captured_piece(): Piece;
+  }
+
   export interface PieceInfo {
     piece: Piece;
     player: Player;
   }
 
+  export enum PieceStyle {
+    Kanji = 0,
+    Tile = 1,
+  }
+
+  export class SvgOptions {
+    constructor();
+    set_flipped(flipped: boolean): void;
+    set_last_move(mv: Move): void;
+    set_piece_style(style: PieceStyle): void;
+    set_font_family(font_family: string): void;
+    add_arrow(from_row: number, from_col: number, to_row: number, to_col: number): void;
+  }
+
+  export class OcrEvaluation {
+    constructor();
+    add(recognized: Board, truth: Board): void;
+    add_failure(): void;
+    get_positions(): number;
+    get_exact_matches(): number;
+    exact_match_rate(): number;
+    square_accuracy(): number;
+    get_square_errors(): number;
+    get_type_errors(): number;
+    get_side_errors(): number;
+    get_promotion_errors(): number;
+    get_missing_pieces(): number;
+    get_extra_pieces(): number;
+    get_hand_errors(): number;
+    get_hand_error_positions(): number;
+    get_turn_errors(): number;
+    get_confusion(expected: Piece, expected_player: Player, got: Piece, got_player: Player): number;
+    report(max_confusions: number): string;
+  }
+
+  export class MoveTextResult {
+    is_unique(): boolean;
+    get_move(): Move | undefined;
+    get_candidates(): Move[];
+    get_candidate_names(): string[];
+    get_error(): string | undefined;
+  }
+
+  export class MoveOutcome {
+    get_move(): Move;
+    get_player(): Player;
+    get_from(): Position;
+    get_to(): Position;
+    get_piece(): Piece;
+    get_placed_piece(): Piece;
+    is_drop(): boolean;
+    is_promotion(): boolean;
+    is_capture(): boolean;
+    get_captured_piece(): Piece;
+    get_hand_piece(): Piece;
+    is_check(): boolean;
+    get_status(): GameStatus;
+    get_winner(): Player | undefined;
+    get_usi(): string;
+    get_kif(previous_to?: Position): string;
+  }
+
+  export class Clock {
+    constructor(main_time: number, byoyomi: number, periods: number, increment: number);
+    start(now: number, player: Player): void;
+    pause(now: number): void;
+    resume(now: number): void;
+    is_running(): boolean;
+    get_turn(): Player;
+    press(now: number): number;
+    check_time(now: number): Player | undefined;
+    is_time_up(player: Player, now: number): boolean;
+    get_loser(): Player | undefined;
+    get_remaining(player: Player, now: number): number;
+    get_byoyomi_remaining(player: Player, now: number): number;
+    get_periods_left(player: Player, now: number): number;
+    get_consumed(player: Player): number;
+  }
+
+  export class GameResult {
+    constructor(termination: Termination, plies: number, player: Player);
+    get_termination(): Termination;
+    get_plies(): number;
+    get_player(): Player;
+    get_winner(): Player | undefined;
+    get_kif_move_name(): string;
+    get_kif_summary(): string;
+    get_csa(): string;
+    get_jkf(): string;
+  }
+
+  export class CastleMatch {
+    get_name(): string;
+    get_matched(): Position[];
+    get_missing(): Position[];
+    get_completeness(): number;
+    is_complete(): boolean;
+  }
+
+  export class BlockedLine {
+    get_blocker(): Position;
+    get_slider(): Position;
+    get_target(): Position;
+    get_line(): Position[];
+  }
+
   export class Board {
     constructor();
+    static from_sfen(sfen: string): Board;
+    static from_bod(text: string): Board;
+    static from_handicap(kind: Handicap): Board;
+    static from_js_value(value: any): Board;
+    static random_playout(seed: bigint, plies: number): Board;
     get_piece(position: Position): PieceInfo;
     get_piece_by_coords(row: number, col: number): PieceInfo;
     is_valid_move(from: Position, to: Position): boolean;
@@ -72,11 +286,64 @@ declare module 'shogi_core' {
     use_captured_piece(player: Player, piece: Piece): boolean;
     can_drop_piece(piece: Piece, to_row: number, to_col: number): boolean;
     drop_piece(piece: Piece, to_row: number, to_col: number): boolean;
-    debug_board_state(): string;
+    make_move_with_outcome(from: Position, to: Position, promote: boolean): MoveOutcome | undefined;
+    drop_piece_with_outcome(piece: Piece, to_row: number, to_col: number): MoveOutcome | undefined;
+    attackers_of(position: Position, player: Player): Position[];
+    is_square_attacked(position: Position, player: Player): boolean;
+    attack_counts(): Uint8Array;
+    pinned_pieces(player: Player): BlockedLine[];
+    discovered_attacks(player: Player): BlockedLine[];
+    see(mv: Move): number;
+    hanging_pieces(player: Player): Position[];
+    declaration_points(player: Player): number;
+    declare_win(rule: DeclarationRule): DeclarationResult;
+    declare_win_on_clock(rule: DeclarationRule, clock: Clock, now: number): DeclarationResult;
+    game_status(): GameStatus;
+    termination(): Termination | undefined;
+    castles(player: Player): CastleMatch[];
+    set_try_rule(enabled: boolean): void;
+    is_try_rule(): boolean;
+    get_winner(): Player | undefined;
+    to_bod(): string;
+    to_svg(options: SvgOptions): string;
+    move_from_usi(usi: string): Move;
+    parse_move_text(text: string, previous_to?: Position): MoveTextResult;
     debug_can_drop_piece(piece: Piece, to_row: number, to_col: number): string;
     debug_has_pawn_in_column(col: number, player: Player, except_row: number): string;
     debug_captured_pieces(): string;
+    is_in_check(player: Player): boolean;
+    generate_legal_moves(): Move[];
+    generate_checks(): Move[];
+    generate_evasions(): Move[];
+    do_move(move: Move): UndoInfo;
+    undo_move(move: Move, undo: UndoInfo): void;
+    get_hash(): bigint;
+    is_king_captured(): boolean;
+    get_piece_box_count(piece: Piece): number;
+    remaining_in_box(): Int32Array;
+    edit_place_from_box(row: number, col: number, piece: Piece, player: Player): boolean;
+    edit_move_on_board(from_row: number, from_col: number, to_row: number, to_col: number): boolean;
+    edit_board_to_hand(row: number, col: number, player: Player): boolean;
+    edit_board_to_box(row: number, col: number): boolean;
+    edit_hand_to_board(player: Player, piece: Piece, row: number, col: number): boolean;
+    edit_hand_to_hand(from: Player, to: Player, piece: Piece): boolean;
+    edit_hand_to_box(player: Player, piece: Piece): boolean;
+    edit_box_to_hand(piece: Piece, player: Player): boolean;
+    edit_flip_promotion(row: number, col: number): boolean;
+    edit_flip_side(row: number, col: number): boolean;
+    edit_cycle_piece(row: number, col: number): boolean;
+    edit_send_box_to_hand(player: Player): void;
+    perft(depth: number): bigint;
+    divide(depth: number): string[];
+    to_sfen(): string;
+    to_js_value(): any;
+    validate(): ValidationIssue[];
+    suggest_corrections(confidence: Float64Array, max_results: number): CorrectionCandidate[];
   }
 
   export function hello_shogi(): string;
+  export function infer_moves(before: Board, after: Board, max_plies: number): InferredMoves[];
+  export function handicap_kif_name(kind: Handicap): string;
+  export function handicap_csa_header(kind: Handicap): string;
+  export function format_kif_time(move_time: number, total_time: number): string;
 } 
\ No newline at end of file
@@ -0,0 +1,54 @@
+// 利きの調べもの（どの駒がどのマスに何枚利いているか）
+//
+// 盤面の利きを色の濃さで表示する学習用の機能や、駒の取り合いの評価に使う
+
+use wasm_bindgen::prelude::*;
+
+use crate::bitboard::{position_of, square_of, tables};
+use crate::{Board, Player, Position};
+
+#[wasm_bindgen]
+impl Board {
+    // 指定したマスに利いている player 側の駒の位置
+    #[wasm_bindgen]
+    pub fn attackers_of(&self, position: Position, player: Player) -> Vec<Position> {
+        if !position.is_valid() {
+            return Vec::new();
+        }
+        self.attackers_to_square(square_of(position), player, self.occupied()).map(position_of).collect()
+    }
+
+    // 指定したマスに player 側の駒が1枚でも利いているか
+    #[wasm_bindgen]
+    pub fn is_square_attacked(&self, position: Position, player: Player) -> bool {
+        position.is_valid() && !self.attackers_to_square(square_of(position), player, self.occupied()).is_empty()
+    }
+
+    // マスごとの利きの数（先手の81マス、後手の81マスの順。マス番号は 段 * 9 + 筋）
+    // 自分の駒があるマスへの利き（ひも）も数える
+    #[wasm_bindgen]
+    pub fn attack_counts(&self) -> Vec<u8> {
+        let mut counts = vec![0; 2 * 81];
+        for player in [Player::Black, Player::White] {
+            for (sq, count) in self.player_attack_counts(player).into_iter().enumerate() {
+                counts[player as usize * 81 + sq] = count;
+            }
+        }
+        counts
+    }
+}
+
+impl Board {
+    fn player_attack_counts(&self, player: Player) -> [u8; 81] {
+        let t = tables();
+        let occupied = self.occupied();
+        let mut counts = [0; 81];
+        for from in self.by_player[player as usize] {
+            let (piece, _) = self.pieces[from];
+            for to in t.attacks(piece, player, from, occupied) {
+                counts[to] += 1;
+            }
+        }
+        counts
+    }
+}
@@ -0,0 +1,64 @@
+// 局面図をSVGで標準出力に書き出す
+//
+// 使い方: board_svg [--flip] [--last <USI>] [--arrow <USI>]... [SFEN]
+// SFENを省略した場合は標準入力から柿木形式の局面図を読む
+// 例: board_svg --last 7g7f "lnsgkgsnl/1r5b1/ppppppppp/9/9/2P6/PP1PPPPPP/1B5R1/LNSGKGSNL w - 2"
+
+use std::io::Read;
+use std::process::ExitCode;
+
+use shogi_core::{Board, SvgOptions};
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(svg) => {
+            print!("{}", svg);
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<String, String> {
+    let mut flipped = false;
+    let mut last_move = None;
+    let mut arrows = Vec::new();
+    let mut sfen = Vec::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--flip" => flipped = true,
+            "--last" => last_move = Some(args.next().ok_or("--last に指し手がありません")?),
+            "--arrow" => arrows.push(args.next().ok_or("--arrow に指し手がありません")?),
+            _ => sfen.push(arg),
+        }
+    }
+
+    let board = if sfen.is_empty() {
+        let mut text = String::new();
+        std::io::stdin().read_to_string(&mut text).map_err(|e| e.to_string())?;
+        Board::from_bod(&text)?
+    } else {
+        Board::from_sfen(&sfen.join(" "))?
+    };
+
+    let mut options = SvgOptions::new();
+    options.set_flipped(flipped);
+    if let Some(usi) = last_move {
+        options.set_last_move(&board.move_from_usi(&usi)?);
+    }
+    for usi in arrows {
+        let mv = board.move_from_usi(&usi)?;
+        if mv.is_drop() {
+            return Err(format!("打つ手は矢印にできません: {}", usi));
+        }
+        let (from, to) = (mv.get_from(), mv.get_to());
+        options.add_arrow(from.get_row(), from.get_column(), to.get_row(), to.get_column());
+    }
+
+    Ok(board.to_svg(&options))
+}
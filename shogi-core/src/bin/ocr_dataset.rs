@@ -0,0 +1,197 @@
+// OCR用の学習・評価データ（局面の画像と正解JSONの組）を作る
+//
+// 使い方: ocr_dataset --out <DIR> [--count 100] [--seed 1] [--max-plies 150] [--font-dir <DIR>]...
+// <DIR>/00000.png と <DIR>/00000.json（フロントエンドの RecognizedPosition と同じ形）を count 組書き出す。
+// 書体はシステムのフォントと --font-dir のフォントのうち、駒の字をすべて持つものから選ぶ
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use resvg::{tiny_skia, usvg};
+use shogi_core::{Board, PieceStyle, Rng, SvgOptions};
+
+// 盤面の画像に書く駒の字（svg の駒の表記と同じ）。持たないフォントでは豆腐になる
+const PIECE_CHARS: &str = "歩香桂銀金角飛玉と杏圭全馬龍";
+
+struct Args {
+    out: PathBuf,
+    count: u32,
+    seed: u64,
+    max_plies: u32,
+    font_dirs: Vec<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    match parse_args().and_then(|args| run(&args)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args { out: PathBuf::new(), count: 100, seed: 1, max_plies: 150, font_dirs: Vec::new() };
+    let mut iter = std::env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or(format!("{} に値がありません", arg));
+        match arg.as_str() {
+            "--out" => args.out = PathBuf::from(value()?),
+            "--count" => args.count = value()?.parse().map_err(|_| "--count が不正です")?,
+            "--seed" => args.seed = value()?.parse().map_err(|_| "--seed が不正です")?,
+            "--max-plies" => args.max_plies = value()?.parse().map_err(|_| "--max-plies が不正です")?,
+            "--font-dir" => args.font_dirs.push(PathBuf::from(value()?)),
+            _ => return Err(format!("不明な引数: {}", arg)),
+        }
+    }
+    if args.out.as_os_str().is_empty() {
+        return Err("使い方: ocr_dataset --out <DIR> [--count N] [--seed N] [--max-plies N] [--font-dir DIR]".to_string());
+    }
+    Ok(args)
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    std::fs::create_dir_all(&args.out).map_err(|e| e.to_string())?;
+
+    let mut usvg_options = usvg::Options::default();
+    let fontdb = usvg_options.fontdb_mut();
+    fontdb.load_system_fonts();
+    for dir in &args.font_dirs {
+        fontdb.load_fonts_dir(dir);
+    }
+    let mut families: Vec<String> = fontdb
+        .faces()
+        .filter(|face| covers_piece_chars(fontdb, face.id))
+        .filter_map(|face| face.families.first().map(|(name, _)| name.clone()))
+        .collect();
+    families.sort();
+    families.dedup();
+    if families.is_empty() {
+        return Err("駒の字を持つフォントが見つかりません。--font-dir で日本語フォントの場所を指定してください".to_string());
+    }
+
+    let mut rng = Rng::new(args.seed);
+    for i in 0..args.count {
+        let plies = rng.below(args.max_plies as usize + 1) as u32;
+        let board = Board::random_playout(rng.next_u64(), plies);
+
+        let mut options = SvgOptions::new();
+        options.set_font_family(&families[rng.below(families.len())]);
+        options.set_piece_style(if rng.below(2) == 0 { PieceStyle::Kanji } else { PieceStyle::Tile });
+        let svg = board.to_svg(&options);
+
+        let scale = 1.0 + rng.unit() as f32;
+        let image = render(&svg, &usvg_options, scale)?;
+        let image = distort(&image, &mut rng);
+
+        let stem = args.out.join(format!("{:05}", i));
+        image.save_png(stem.with_extension("png")).map_err(|e| e.to_string())?;
+        write_json(&stem.with_extension("json"), &board)?;
+    }
+    eprintln!("{} 組を {} に書き出しました", args.count, args.out.display());
+    Ok(())
+}
+
+fn covers_piece_chars(fontdb: &usvg::fontdb::Database, id: usvg::fontdb::ID) -> bool {
+    fontdb
+        .with_face_data(id, |data, index| {
+            ttf_parser::Face::parse(data, index)
+                .map(|face| PIECE_CHARS.chars().all(|c| face.glyph_index(c).is_some()))
+                .unwrap_or(false)
+        })
+        .unwrap_or(false)
+}
+
+fn render(svg: &str, options: &usvg::Options, scale: f32) -> Result<tiny_skia::Pixmap, String> {
+    let tree = usvg::Tree::from_str(svg, options).map_err(|e| e.to_string())?;
+    let size = tree.size().to_int_size().scale_by(scale).ok_or("画像の大きさが不正です")?;
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height()).ok_or("画像を作れません")?;
+    resvg::render(&tree, tiny_skia::Transform::from_scale(scale, scale), &mut pixmap.as_mut());
+    Ok(pixmap)
+}
+
+fn write_json(path: &Path, board: &Board) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(board).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| e.to_string())
+}
+
+// 写真らしく見えるように、斜めから撮ったような歪みと明るさのむら・ノイズを加える
+fn distort(source: &tiny_skia::Pixmap, rng: &mut Rng) -> tiny_skia::Pixmap {
+    let (width, height) = (source.width() as f64, source.height() as f64);
+    let corners = [(0.0, 0.0), (width, 0.0), (width, height), (0.0, height)];
+    // 四隅を最大で幅・高さの8%ずらす
+    let moved = corners.map(|(x, y)| (x + (rng.unit() - 0.5) * 0.16 * width, y + (rng.unit() - 0.5) * 0.16 * height));
+    let to_source = homography(moved, corners);
+
+    let background = 160.0 + rng.unit() * 80.0;
+    let brightness = (rng.unit() - 0.5) * 40.0;
+    let noise = rng.unit() * 24.0;
+
+    let mut output = tiny_skia::Pixmap::new(source.width(), source.height()).expect("元の画像と同じ大きさ");
+    let pixels = source.pixels();
+    for y in 0..source.height() {
+        for x in 0..source.width() {
+            let (sx, sy) = apply(&to_source, x as f64 + 0.5, y as f64 + 0.5);
+            let mut rgb = sample(pixels, source.width(), source.height(), sx - 0.5, sy - 0.5).unwrap_or([background; 3]);
+            let grain = (rng.unit() - 0.5) * noise;
+            for channel in &mut rgb {
+                *channel = (*channel + brightness + grain).clamp(0.0, 255.0);
+            }
+            let [r, g, b] = rgb.map(|c| c as u8);
+            output.pixels_mut()[(y * source.width() + x) as usize] =
+                tiny_skia::PremultipliedColorU8::from_rgba(r, g, b, 255).expect("不透明な色");
+        }
+    }
+    output
+}
+
+// 双線形補間で色を取る（画像の外なら None）
+fn sample(pixels: &[tiny_skia::PremultipliedColorU8], width: u32, height: u32, x: f64, y: f64) -> Option<[f64; 3]> {
+    if x < 0.0 || y < 0.0 || x > (width - 1) as f64 || y > (height - 1) as f64 {
+        return None;
+    }
+    let (x0, y0) = (x.floor() as u32, y.floor() as u32);
+    let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+    let (fx, fy) = (x - x0 as f64, y - y0 as f64);
+    let at = |px: u32, py: u32| {
+        let c = pixels[(py * width + px) as usize];
+        [c.red() as f64, c.green() as f64, c.blue() as f64]
+    };
+    let (a, b, c, d) = (at(x0, y0), at(x1, y0), at(x0, y1), at(x1, y1));
+    Some(std::array::from_fn(|i| {
+        let top = a[i] * (1.0 - fx) + b[i] * fx;
+        let bottom = c[i] * (1.0 - fx) + d[i] * fx;
+        top * (1.0 - fy) + bottom * fy
+    }))
+}
+
+// 四角形 from の四隅を to の四隅に移す射影変換の行列
+fn homography(from: [(f64, f64); 4], to: [(f64, f64); 4]) -> [f64; 9] {
+    let mut a = [[0.0; 9]; 8];
+    for (i, (&(x, y), &(u, v))) in from.iter().zip(to.iter()).enumerate() {
+        a[2 * i] = [x, y, 1.0, 0.0, 0.0, 0.0, -u * x, -u * y, u];
+        a[2 * i + 1] = [0.0, 0.0, 0.0, x, y, 1.0, -v * x, -v * y, v];
+    }
+    // ガウスの消去法（部分ピボット選択）
+    for col in 0..8 {
+        let pivot = (col..8).max_by(|&p, &q| a[p][col].abs().total_cmp(&a[q][col].abs())).unwrap_or(col);
+        a.swap(col, pivot);
+        let pivot_row = a[col];
+        for (row, values) in a.iter_mut().enumerate() {
+            if row != col {
+                let factor = values[col] / pivot_row[col];
+                for (value, pivot) in values.iter_mut().zip(pivot_row).skip(col) {
+                    *value -= factor * pivot;
+                }
+            }
+        }
+    }
+    let h: [f64; 8] = std::array::from_fn(|i| a[i][8] / a[i][i]);
+    [h[0], h[1], h[2], h[3], h[4], h[5], h[6], h[7], 1.0]
+}
+
+fn apply(h: &[f64; 9], x: f64, y: f64) -> (f64, f64) {
+    let w = h[6] * x + h[7] * y + h[8];
+    ((h[0] * x + h[1] * y + h[2]) / w, (h[3] * x + h[4] * y + h[5]) / w)
+}
@@ -0,0 +1,71 @@
+// 盤面OCRの精度を、正解データのディレクトリと読み取り結果のディレクトリを比べて集計する
+//
+// 使い方: ocr_eval --truth <DIR> --recognized <DIR> [--top 10]
+// --truth の *.json（ocr_dataset が書き出したもの）ごとに、--recognized の同じ名前の JSON
+// （RecognizedPosition の形）を読んで比べる。読み取り結果がない・読めない局面は不一致として数える
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use shogi_core::{Board, OcrEvaluation};
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(report) => {
+            print!("{}", report);
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<String, String> {
+    let mut truth_dir = None;
+    let mut recognized_dir = None;
+    let mut top = 10;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} に値がありません", arg));
+        match arg.as_str() {
+            "--truth" => truth_dir = Some(PathBuf::from(value()?)),
+            "--recognized" => recognized_dir = Some(PathBuf::from(value()?)),
+            "--top" => top = value()?.parse().map_err(|_| "--top が不正です")?,
+            _ => return Err(format!("不明な引数: {}", arg)),
+        }
+    }
+    let (Some(truth_dir), Some(recognized_dir)) = (truth_dir, recognized_dir) else {
+        return Err("使い方: ocr_eval --truth <DIR> --recognized <DIR> [--top N]".to_string());
+    };
+
+    let mut files: Vec<PathBuf> = std::fs::read_dir(&truth_dir)
+        .map_err(|e| format!("{}: {}", truth_dir.display(), e))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+    if files.is_empty() {
+        return Err(format!("{} に正解の JSON がありません", truth_dir.display()));
+    }
+
+    let mut evaluation = OcrEvaluation::new();
+    for truth_path in files {
+        let truth = read_board(&truth_path)?;
+        let recognized_path = recognized_dir.join(truth_path.file_name().unwrap_or_default());
+        match read_board(&recognized_path) {
+            Ok(recognized) => evaluation.add(&recognized, &truth),
+            Err(message) => {
+                eprintln!("{}", message);
+                evaluation.add_failure();
+            }
+        }
+    }
+    Ok(evaluation.report(top))
+}
+
+fn read_board(path: &Path) -> Result<Board, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    serde_json::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))
+}
@@ -0,0 +1,319 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+use std::sync::OnceLock;
+
+use crate::movegen::ALL_PIECES;
+use crate::{Piece, Player, Position};
+
+// 81マスの盤面を1ビットずつ表現する（マス番号 = 段 * 9 + 筋）
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct Bitboard(u128);
+
+const BOARD_MASK: u128 = (1u128 << 81) - 1;
+
+// 9筋目（列0）のマス
+const FILE_MASK: u128 = {
+    let mut mask = 0u128;
+    let mut row = 0;
+    while row < 9 {
+        mask |= 1u128 << (row * 9);
+        row += 1;
+    }
+    mask
+};
+
+impl Bitboard {
+    pub(crate) const EMPTY: Bitboard = Bitboard(0);
+
+    pub(crate) fn from_square(sq: usize) -> Bitboard {
+        Bitboard(1u128 << sq)
+    }
+
+    pub(crate) fn has(self, sq: usize) -> bool {
+        self.0 & (1u128 << sq) != 0
+    }
+
+    pub(crate) fn set(&mut self, sq: usize) {
+        self.0 |= 1u128 << sq;
+    }
+
+    pub(crate) fn clear(&mut self, sq: usize) {
+        self.0 &= !(1u128 << sq);
+    }
+
+    pub(crate) fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub(crate) fn count(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    // 最も番号の小さいマス
+    pub(crate) fn lsb(self) -> usize {
+        self.0.trailing_zeros() as usize
+    }
+
+    // 最も番号の大きいマス
+    pub(crate) fn msb(self) -> usize {
+        127 - self.0.leading_zeros() as usize
+    }
+
+    // 指定した段（0-8）のマス
+    pub(crate) fn rank(row: usize) -> Bitboard {
+        Bitboard(0x1ffu128 << (row * 9))
+    }
+
+    // 指定した筋（列 0-8）のマス
+    pub(crate) fn file(col: usize) -> Bitboard {
+        Bitboard(FILE_MASK << col)
+    }
+
+    // 指定したプレイヤーから見た敵陣（3段）
+    pub(crate) fn promotion_zone(player: Player) -> Bitboard {
+        match player {
+            Player::Black => Bitboard::rank(6) | Bitboard::rank(7) | Bitboard::rank(8),
+            Player::White => Bitboard::rank(0) | Bitboard::rank(1) | Bitboard::rank(2),
+        }
+    }
+}
+
+impl Iterator for Bitboard {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.0 == 0 {
+            None
+        } else {
+            let sq = self.lsb();
+            self.0 &= self.0 - 1;
+            Some(sq)
+        }
+    }
+}
+
+impl BitAnd for Bitboard {
+    type Output = Bitboard;
+    fn bitand(self, rhs: Bitboard) -> Bitboard {
+        Bitboard(self.0 & rhs.0)
+    }
+}
+
+impl BitOr for Bitboard {
+    type Output = Bitboard;
+    fn bitor(self, rhs: Bitboard) -> Bitboard {
+        Bitboard(self.0 | rhs.0)
+    }
+}
+
+impl BitXor for Bitboard {
+    type Output = Bitboard;
+    fn bitxor(self, rhs: Bitboard) -> Bitboard {
+        Bitboard(self.0 ^ rhs.0)
+    }
+}
+
+impl Not for Bitboard {
+    type Output = Bitboard;
+    fn not(self) -> Bitboard {
+        Bitboard(!self.0 & BOARD_MASK)
+    }
+}
+
+impl BitAndAssign for Bitboard {
+    fn bitand_assign(&mut self, rhs: Bitboard) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl BitOrAssign for Bitboard {
+    fn bitor_assign(&mut self, rhs: Bitboard) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitXorAssign for Bitboard {
+    fn bitxor_assign(&mut self, rhs: Bitboard) {
+        self.0 ^= rhs.0;
+    }
+}
+
+pub(crate) fn square(row: usize, col: usize) -> usize {
+    row * 9 + col
+}
+
+pub(crate) fn square_of(pos: Position) -> usize {
+    square(pos.row as usize, pos.column as usize)
+}
+
+pub(crate) fn position_of(sq: usize) -> Position {
+    Position {
+        row: (sq / 9) as i32,
+        column: (sq % 9) as i32,
+    }
+}
+
+// 8方向（段の増減, 筋の増減）。番号が増える方向は4以降
+pub(crate) const DIRECTIONS: [(i32, i32); 8] = [
+    (-1, -1), (-1, 0), (-1, 1),
+    (0, -1), (0, 1),
+    (1, -1), (1, 0), (1, 1),
+];
+
+const ORTHOGONAL_DIRECTIONS: [usize; 4] = [1, 3, 4, 6];
+const DIAGONAL_DIRECTIONS: [usize; 4] = [0, 2, 5, 7];
+
+// 先手から見て前方向の方向番号
+fn forward_direction(player: Player) -> usize {
+    match player {
+        Player::Black => 6,
+        Player::White => 1,
+    }
+}
+
+// 事前計算した利きのテーブル
+pub(crate) struct AttackTables {
+    // [手番][駒][マス] 1マスだけ動く利き（馬・龍は追加の1マス分）
+    step: [[[Bitboard; 81]; 15]; 2],
+    // [方向][マス] そのマスから盤端までの直線（自マスを含まない）
+    rays: [[Bitboard; 81]; 8],
+    // [a * 81 + b] 2マスの間（両端を含まない）
+    between: Vec<Bitboard>,
+    // [a * 81 + b] 2マスを通る直線全体（直線上にない場合は空）
+    line: Vec<Bitboard>,
+}
+
+static TABLES: OnceLock<AttackTables> = OnceLock::new();
+
+pub(crate) fn tables() -> &'static AttackTables {
+    TABLES.get_or_init(AttackTables::build)
+}
+
+fn offset(sq: usize, dr: i32, dc: i32) -> Option<usize> {
+    let row = (sq / 9) as i32 + dr;
+    let col = (sq % 9) as i32 + dc;
+    if (0..9).contains(&row) && (0..9).contains(&col) {
+        Some(square(row as usize, col as usize))
+    } else {
+        None
+    }
+}
+
+// 先手から見た1マスの動き（後手は段の増減を反転する）
+fn step_offsets(piece: Piece) -> &'static [(i32, i32)] {
+    match piece {
+        Piece::Pawn => &[(1, 0)],
+        Piece::Knight => &[(2, -1), (2, 1)],
+        Piece::Silver => &[(1, -1), (1, 0), (1, 1), (-1, -1), (-1, 1)],
+        Piece::Gold
+        | Piece::PromotedPawn
+        | Piece::PromotedLance
+        | Piece::PromotedKnight
+        | Piece::PromotedSilver => &[(1, -1), (1, 0), (1, 1), (0, -1), (0, 1), (-1, 0)],
+        Piece::King => &[(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)],
+        Piece::PromotedBishop => &[(-1, 0), (1, 0), (0, -1), (0, 1)],
+        Piece::PromotedRook => &[(-1, -1), (-1, 1), (1, -1), (1, 1)],
+        _ => &[],
+    }
+}
+
+impl AttackTables {
+    fn build() -> AttackTables {
+        let mut step = [[[Bitboard::EMPTY; 81]; 15]; 2];
+        for (player_index, sign) in [(0usize, 1i32), (1, -1)] {
+            for piece in ALL_PIECES {
+                for (sq, attacks) in step[player_index][piece as usize].iter_mut().enumerate() {
+                    for &(dr, dc) in step_offsets(piece) {
+                        if let Some(to) = offset(sq, dr * sign, dc) {
+                            attacks.set(to);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut rays = [[Bitboard::EMPTY; 81]; 8];
+        for (dir, &(dr, dc)) in DIRECTIONS.iter().enumerate() {
+            for (sq, ray) in rays[dir].iter_mut().enumerate() {
+                let mut current = offset(sq, dr, dc);
+                while let Some(to) = current {
+                    ray.set(to);
+                    current = offset(to, dr, dc);
+                }
+            }
+        }
+
+        let mut between = vec![Bitboard::EMPTY; 81 * 81];
+        let mut line = vec![Bitboard::EMPTY; 81 * 81];
+        for a in 0..81 {
+            for (dir, &(dr, dc)) in DIRECTIONS.iter().enumerate() {
+                let opposite = 7 - dir;
+                let full_line = rays[dir][a] | rays[opposite][a] | Bitboard::from_square(a);
+                let mut squares = Bitboard::EMPTY;
+                let mut current = offset(a, dr, dc);
+                while let Some(b) = current {
+                    between[a * 81 + b] = squares;
+                    line[a * 81 + b] = full_line;
+                    squares.set(b);
+                    current = offset(b, dr, dc);
+                }
+            }
+        }
+
+        AttackTables { step, rays, between, line }
+    }
+
+    pub(crate) fn step_attacks(&self, piece: Piece, player: Player, sq: usize) -> Bitboard {
+        self.step[player as usize][piece as usize][sq]
+    }
+
+    pub(crate) fn between(&self, a: usize, b: usize) -> Bitboard {
+        self.between[a * 81 + b]
+    }
+
+    pub(crate) fn line(&self, a: usize, b: usize) -> Bitboard {
+        self.line[a * 81 + b]
+    }
+
+    // 1方向の飛び利き（最初にぶつかった駒のマスまで）
+    pub(crate) fn ray_attacks(&self, dir: usize, sq: usize, occupied: Bitboard) -> Bitboard {
+        let ray = self.rays[dir][sq];
+        let blockers = ray & occupied;
+        if blockers.is_empty() {
+            return ray;
+        }
+        let nearest = if dir >= 4 { blockers.lsb() } else { blockers.msb() };
+        ray ^ self.rays[dir][nearest]
+    }
+
+    pub(crate) fn lance_attacks(&self, player: Player, sq: usize, occupied: Bitboard) -> Bitboard {
+        self.ray_attacks(forward_direction(player), sq, occupied)
+    }
+
+    pub(crate) fn rook_attacks(&self, sq: usize, occupied: Bitboard) -> Bitboard {
+        let mut bb = Bitboard::EMPTY;
+        for dir in ORTHOGONAL_DIRECTIONS {
+            bb |= self.ray_attacks(dir, sq, occupied);
+        }
+        bb
+    }
+
+    pub(crate) fn bishop_attacks(&self, sq: usize, occupied: Bitboard) -> Bitboard {
+        let mut bb = Bitboard::EMPTY;
+        for dir in DIAGONAL_DIRECTIONS {
+            bb |= self.ray_attacks(dir, sq, occupied);
+        }
+        bb
+    }
+
+    // 駒の利き（味方の駒がいるマスも含む）
+    pub(crate) fn attacks(&self, piece: Piece, player: Player, sq: usize, occupied: Bitboard) -> Bitboard {
+        match piece {
+            Piece::Lance => self.lance_attacks(player, sq, occupied),
+            Piece::Bishop => self.bishop_attacks(sq, occupied),
+            Piece::Rook => self.rook_attacks(sq, occupied),
+            Piece::PromotedBishop => self.bishop_attacks(sq, occupied) | self.step_attacks(piece, player, sq),
+            Piece::PromotedRook => self.rook_attacks(sq, occupied) | self.step_attacks(piece, player, sq),
+            _ => self.step_attacks(piece, player, sq),
+        }
+    }
+}
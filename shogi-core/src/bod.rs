@@ -0,0 +1,184 @@
+// 柿木形式（BOD）の局面図
+//
+// 後手の持駒：角　歩二
+//   ９ ８ ７ ６ ５ ４ ３ ２ １
+// +---------------------------+
+// |v香v桂v銀v金v玉v金v銀v桂v香|一
+// ...
+// +---------------------------+
+// 先手の持駒：なし
+// 先手番
+
+use wasm_bindgen::prelude::*;
+
+use crate::bitboard::square;
+use crate::movegen::HAND_PIECES;
+use crate::notation::{kanji_number, parse_kanji_number, piece_char, piece_name, player_name};
+use crate::{Board, Piece, Player};
+
+const FILE_HEADER: &str = "  ９ ８ ７ ６ ５ ４ ３ ２ １";
+const BORDER: &str = "+---------------------------+";
+const RANKS: [char; 9] = ['一', '二', '三', '四', '五', '六', '七', '八', '九'];
+
+fn piece_from_bod_char(c: char) -> Option<Piece> {
+    let piece = match c {
+        '歩' => Piece::Pawn,
+        '香' => Piece::Lance,
+        '桂' => Piece::Knight,
+        '銀' => Piece::Silver,
+        '金' => Piece::Gold,
+        '角' => Piece::Bishop,
+        '飛' => Piece::Rook,
+        '玉' | '王' => Piece::King,
+        'と' => Piece::PromotedPawn,
+        '杏' => Piece::PromotedLance,
+        '圭' => Piece::PromotedKnight,
+        '全' => Piece::PromotedSilver,
+        '馬' => Piece::PromotedBishop,
+        '龍' | '竜' => Piece::PromotedRook,
+        _ => return None,
+    };
+    Some(piece)
+}
+
+// 「先手の持駒：」などの行の持ち主（駒落ちの上手・下手も受け付ける）
+fn hand_owner(label: &str) -> Option<Player> {
+    if label.starts_with("先手") || label.starts_with("下手") {
+        Some(Player::Black)
+    } else if label.starts_with("後手") || label.starts_with("上手") {
+        Some(Player::White)
+    } else {
+        None
+    }
+}
+
+#[wasm_bindgen]
+impl Board {
+    // 柿木形式の局面図に変換する
+    #[wasm_bindgen]
+    pub fn to_bod(&self) -> String {
+        let mut lines = Vec::new();
+        lines.push(self.bod_hand_line(Player::White));
+        lines.push(FILE_HEADER.to_string());
+        lines.push(BORDER.to_string());
+        for (i, rank) in RANKS.iter().enumerate() {
+            let row = 8 - i;
+            let mut line = String::from("|");
+            for col in 0..9 {
+                match self.pieces[square(row, col)] {
+                    (Piece::Empty, _) => line.push_str(" ・"),
+                    (piece, player) => {
+                        line.push(if player == Player::White { 'v' } else { ' ' });
+                        line.push(piece_char(piece));
+                    }
+                }
+            }
+            line.push('|');
+            line.push(*rank);
+            lines.push(line);
+        }
+        lines.push(BORDER.to_string());
+        lines.push(self.bod_hand_line(Player::Black));
+        lines.push(format!("{}番", player_name(self.current_player)));
+        lines.join("\n")
+    }
+
+    // 柿木形式の局面図から盤面を作成する（手数などの局面図以外の行は無視する）
+    #[wasm_bindgen]
+    pub fn from_bod(text: &str) -> Result<Board, String> {
+        let mut board = Board::new();
+        board.clear_board();
+        board.clear_captured_pieces(Player::Black);
+        board.clear_captured_pieces(Player::White);
+
+        let mut rows = 0;
+        let mut player = Player::Black;
+        for line in text.lines().map(str::trim_end) {
+            if let Some(body) = line.strip_prefix('|') {
+                if rows >= 9 {
+                    return Err("局面図の段が多すぎます".to_string());
+                }
+                let cells = body.split('|').next().unwrap_or("");
+                board.read_bod_rank(8 - rows, cells)?;
+                rows += 1;
+            } else if let Some((label, hand)) = line.split_once(['：', ':']) {
+                if label.ends_with("持駒") || label.ends_with("持ち駒") {
+                    let owner = hand_owner(label).ok_or(format!("持ち駒の持ち主が不明です: {}", label))?;
+                    board.read_bod_hand(owner, hand)?;
+                }
+            } else if line == "先手番" || line == "下手番" {
+                player = Player::Black;
+            } else if line == "後手番" || line == "上手番" {
+                player = Player::White;
+            }
+        }
+        if rows != 9 {
+            return Err(format!("局面図の段の数が不正です: {}", rows));
+        }
+        board.set_current_player(player);
+
+        Ok(board)
+    }
+}
+
+impl Board {
+    fn bod_hand_line(&self, player: Player) -> String {
+        let mut hand = Vec::new();
+        for &piece in HAND_PIECES.iter().rev() {
+            let count = self.get_captured_piece_count(player, piece);
+            if count == 1 {
+                hand.push(piece_name(piece).to_string());
+            } else if count > 1 {
+                hand.push(format!("{}{}", piece_name(piece), kanji_number(count as u32)));
+            }
+        }
+        if hand.is_empty() {
+            hand.push("なし".to_string());
+        }
+        format!("{}の持駒：{}", player_name(player), hand.join("　"))
+    }
+
+    // 1段分のマス（"v香v桂 ・..."、2文字で1マス）を読む
+    fn read_bod_rank(&mut self, row: usize, cells: &str) -> Result<(), String> {
+        let chars: Vec<char> = cells.chars().collect();
+        let rank = 9 - row;
+        if chars.len() != 18 {
+            return Err(format!("{}段目のマス数が不正です", rank));
+        }
+        for (col, cell) in chars.chunks(2).enumerate() {
+            if cell[1] == '・' {
+                continue;
+            }
+            let piece = piece_from_bod_char(cell[1]).ok_or(format!("不明な駒: {}", cell[1]))?;
+            let player = match cell[0] {
+                'v' | 'V' => Player::White,
+                ' ' | '^' => Player::Black,
+                c => return Err(format!("{}段目の駒の向きが不明です: {}", rank, c)),
+            };
+            self.put_piece(square(row, col), piece, player);
+        }
+        Ok(())
+    }
+
+    // 持ち駒（"角　歩二" のように全角空白区切り、"なし" は0枚）を読む
+    fn read_bod_hand(&mut self, player: Player, hand: &str) -> Result<(), String> {
+        for token in hand.split(|c: char| c.is_whitespace()).filter(|t| !t.is_empty() && *t != "なし") {
+            let mut chars = token.chars();
+            let c = chars.next().unwrap_or_default();
+            let piece = piece_from_bod_char(c)
+                .filter(|&piece| HAND_PIECES.contains(&piece))
+                .ok_or(format!("不明な持ち駒: {}", token))?;
+            let rest = chars.as_str();
+            let count = if rest.is_empty() {
+                1
+            } else {
+                parse_kanji_number(rest).ok_or(format!("持ち駒の枚数が不正です: {}", token))? as i32
+            };
+            let current = self.get_captured_piece_count(player, piece);
+            if !self.set_captured_piece_count(player, piece, current + count) {
+                return Err(format!("持ち駒の枚数が不正です: {}", token));
+            }
+        }
+        Ok(())
+    }
+}
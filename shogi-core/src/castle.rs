@@ -0,0 +1,129 @@
+// 囲いの判定（教材の囲いの名前を、組み上げている途中の局面にも付けられるようにする）
+//
+// 囲いは先手から見た駒の配置（筋・段）で書き、後手は盤を180度回して当てはめる。
+// 玉の位置が合っていて、玉以外の駒が半分以上そろっていれば、組み上げている途中の囲いとして返す
+
+use wasm_bindgen::prelude::*;
+
+use crate::bitboard::square;
+use crate::{Board, Piece, Player, Position};
+
+struct CastlePattern {
+    name: &'static str,
+    king: (u8, u8),                    // 玉の位置（筋、段）
+    pieces: &'static [(Piece, u8, u8)], // 玉以外の駒（駒、筋、段）
+}
+
+use Piece::{Gold as G, Knight as N, Lance as L, Pawn as P, Silver as S};
+
+const CASTLES: &[CastlePattern] = &[
+    CastlePattern { name: "矢倉", king: (8, 8), pieces: &[(G, 7, 8), (G, 6, 7), (S, 7, 7), (P, 8, 7), (P, 7, 6), (P, 6, 6)] },
+    CastlePattern { name: "美濃囲い", king: (2, 8), pieces: &[(S, 3, 8), (G, 4, 9), (G, 5, 8), (P, 2, 7)] },
+    CastlePattern { name: "高美濃囲い", king: (2, 8), pieces: &[(S, 3, 8), (G, 4, 9), (G, 4, 7), (P, 2, 7), (P, 4, 6)] },
+    CastlePattern { name: "銀冠", king: (2, 8), pieces: &[(S, 2, 7), (G, 3, 8), (G, 4, 7), (P, 2, 6)] },
+    CastlePattern {
+        name: "居飛車穴熊",
+        king: (9, 9),
+        pieces: &[(L, 9, 8), (N, 8, 9), (S, 8, 8), (G, 7, 9), (P, 9, 7), (P, 8, 7)],
+    },
+    CastlePattern {
+        name: "振り飛車穴熊",
+        king: (1, 9),
+        pieces: &[(L, 1, 8), (N, 2, 9), (S, 2, 8), (G, 3, 9), (P, 1, 7), (P, 2, 7)],
+    },
+    CastlePattern { name: "舟囲い", king: (7, 8), pieces: &[(G, 6, 9), (G, 5, 8), (S, 6, 8)] },
+    CastlePattern { name: "左美濃", king: (8, 8), pieces: &[(S, 7, 8), (G, 6, 9), (G, 5, 8), (P, 8, 7)] },
+    CastlePattern { name: "エルモ囲い", king: (8, 8), pieces: &[(G, 7, 8), (S, 7, 9), (P, 8, 7)] },
+    CastlePattern {
+        name: "雁木",
+        king: (6, 9),
+        pieces: &[(S, 6, 7), (S, 4, 7), (G, 7, 8), (G, 5, 8), (P, 6, 6), (P, 5, 6)],
+    },
+    CastlePattern { name: "中住まい", king: (5, 8), pieces: &[(G, 7, 8), (G, 3, 8)] },
+];
+
+// 見つかった囲い
+#[wasm_bindgen]
+pub struct CastleMatch {
+    name: &'static str,
+    matched: Vec<Position>, // そろっている駒のマス（玉を含む）
+    missing: Vec<Position>, // まだそろっていない駒のマス
+}
+
+#[wasm_bindgen]
+impl CastleMatch {
+    #[wasm_bindgen]
+    pub fn get_name(&self) -> String {
+        self.name.to_string()
+    }
+
+    #[wasm_bindgen]
+    pub fn get_matched(&self) -> Vec<Position> {
+        self.matched.clone()
+    }
+
+    #[wasm_bindgen]
+    pub fn get_missing(&self) -> Vec<Position> {
+        self.missing.clone()
+    }
+
+    // 囲いの駒のうち、そろっている割合（0.0〜1.0）
+    #[wasm_bindgen]
+    pub fn get_completeness(&self) -> f64 {
+        self.matched.len() as f64 / (self.matched.len() + self.missing.len()) as f64
+    }
+
+    #[wasm_bindgen]
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+#[wasm_bindgen]
+impl Board {
+    // player の囲い（組み上げている途中のものを含む。そろっている割合の高い順）
+    #[wasm_bindgen]
+    pub fn castles(&self, player: Player) -> Vec<CastleMatch> {
+        let mut found: Vec<CastleMatch> = CASTLES.iter().filter_map(|pattern| self.match_castle(pattern, player)).collect();
+        found.sort_by(|a, b| b.get_completeness().total_cmp(&a.get_completeness()));
+        found
+    }
+}
+
+impl Board {
+    fn match_castle(&self, pattern: &CastlePattern, player: Player) -> Option<CastleMatch> {
+        let king = castle_position(player, pattern.king);
+        if !self.has_piece_at(king, Piece::King, player) {
+            return None;
+        }
+
+        let mut matched = vec![king];
+        let mut missing = Vec::new();
+        for &(piece, file, rank) in pattern.pieces {
+            let position = castle_position(player, (file, rank));
+            if self.has_piece_at(position, piece, player) {
+                matched.push(position);
+            } else {
+                missing.push(position);
+            }
+        }
+        // 玉以外の駒が半分以上そろっていること
+        if (matched.len() - 1) * 2 < pattern.pieces.len() {
+            return None;
+        }
+        Some(CastleMatch { name: pattern.name, matched, missing })
+    }
+
+    fn has_piece_at(&self, position: Position, piece: Piece, player: Player) -> bool {
+        self.pieces[square(position.row as usize, position.column as usize)] == (piece, player)
+    }
+}
+
+// 先手から見た（筋、段）を player の側のマスにする
+fn castle_position(player: Player, (file, rank): (u8, u8)) -> Position {
+    let (file, rank) = match player {
+        Player::Black => (file as i32, rank as i32),
+        Player::White => (10 - file as i32, 10 - rank as i32),
+    };
+    Position::new(9 - rank, 9 - file)
+}
@@ -0,0 +1,198 @@
+// 対局時計（持ち時間 + 秒読み・フィッシャー・切れ負け）
+//
+// 時刻はすべて呼び出し側が渡す（ミリ秒）。同じ時刻を渡せば同じ結果になるので、テストでも実際の時間を待たずに済む。
+// 秒読みは1回の秒読みを使い切るごとに回数を1つ減らし、0回になったら時間切れ。
+// 秒読みが0秒なら、持ち時間を使い切った時点で時間切れ（切れ負け）
+
+use wasm_bindgen::prelude::*;
+
+use crate::Player;
+
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct Clock {
+    byoyomi: f64,   // 1回の秒読みの時間
+    increment: f64, // 1手ごとに持ち時間に加える時間（フィッシャー）
+    remaining: [f64; 2],
+    periods_left: [u32; 2],
+    consumed: [f64; 2], // 消費時間の合計
+    turn: Player,
+    turn_started: Option<f64>, // 手番の側の時計が動き始めた時刻（止まっている間は None）
+    elapsed: f64,              // 時計を止めるまでに、手番の側がこの手で使った時間
+    loser: Option<Player>,     // 時間切れになった側
+}
+
+// 1手で使った時間を差し引いた結果
+struct Spent {
+    remaining: f64,
+    periods_left: u32,
+    byoyomi_used: f64, // いまの秒読みで使った時間
+    time_up: bool,
+}
+
+#[wasm_bindgen]
+impl Clock {
+    // main_time: 持ち時間、byoyomi: 1回の秒読み、periods: 秒読みの回数、increment: 1手ごとの加算（いずれもミリ秒）
+    #[wasm_bindgen(constructor)]
+    pub fn new(main_time: f64, byoyomi: f64, periods: u32, increment: f64) -> Clock {
+        Clock {
+            byoyomi: byoyomi.max(0.0),
+            increment: increment.max(0.0),
+            remaining: [main_time.max(0.0); 2],
+            periods_left: [periods; 2],
+            consumed: [0.0; 2],
+            turn: Player::Black,
+            turn_started: None,
+            elapsed: 0.0,
+            loser: None,
+        }
+    }
+
+    // player の時計を動かし始める
+    #[wasm_bindgen]
+    pub fn start(&mut self, now: f64, player: Player) {
+        self.turn = player;
+        self.elapsed = 0.0;
+        self.turn_started = Some(now);
+    }
+
+    // 時計を止める（中断など）。その手で使った時間は resume() の後も引き継ぐ
+    #[wasm_bindgen]
+    pub fn pause(&mut self, now: f64) {
+        if let Some(started) = self.turn_started.take() {
+            self.elapsed += (now - started).max(0.0);
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn resume(&mut self, now: f64) {
+        if self.turn_started.is_none() && self.loser.is_none() {
+            self.turn_started = Some(now);
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn is_running(&self) -> bool {
+        self.turn_started.is_some()
+    }
+
+    #[wasm_bindgen]
+    pub fn get_turn(&self) -> Player {
+        self.turn
+    }
+
+    // 手番の側が指し終えて時計を押す。その手の消費時間を返す
+    // 時間切れだった場合は時計が止まり、get_loser() が手番の側になる
+    #[wasm_bindgen]
+    pub fn press(&mut self, now: f64) -> f64 {
+        let player = self.turn;
+        let elapsed = self.elapsed_at(now);
+        let spent = self.spend(player, elapsed);
+        self.consumed[player as usize] += elapsed;
+        self.remaining[player as usize] = spent.remaining;
+        self.periods_left[player as usize] = spent.periods_left;
+        self.elapsed = 0.0;
+        if spent.time_up {
+            self.loser = Some(player);
+            self.turn_started = None;
+        } else {
+            self.remaining[player as usize] += self.increment;
+            self.turn = player.opponent();
+            self.turn_started = Some(now);
+        }
+        elapsed
+    }
+
+    // 手番の側が now の時点で時間切れになっていれば、時計を止めてその側を返す
+    #[wasm_bindgen]
+    pub fn check_time(&mut self, now: f64) -> Option<Player> {
+        if self.loser.is_none() && self.is_running() && self.spend(self.turn, self.elapsed_at(now)).time_up {
+            self.pause(now);
+            self.loser = Some(self.turn);
+        }
+        self.loser
+    }
+
+    // player が now の時点で時間切れになっているか
+    #[wasm_bindgen]
+    pub fn is_time_up(&self, player: Player, now: f64) -> bool {
+        self.loser == Some(player) || self.spent_at(player, now).time_up
+    }
+
+    // 時間切れで負けた側
+    #[wasm_bindgen]
+    pub fn get_loser(&self) -> Option<Player> {
+        self.loser
+    }
+
+    // now の時点の持ち時間の残り
+    #[wasm_bindgen]
+    pub fn get_remaining(&self, player: Player, now: f64) -> f64 {
+        self.spent_at(player, now).remaining
+    }
+
+    // now の時点の、いまの秒読みの残り（秒読みに入っていなければ秒読み1回分）
+    #[wasm_bindgen]
+    pub fn get_byoyomi_remaining(&self, player: Player, now: f64) -> f64 {
+        let spent = self.spent_at(player, now);
+        if spent.time_up {
+            0.0
+        } else {
+            self.byoyomi - spent.byoyomi_used
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn get_periods_left(&self, player: Player, now: f64) -> u32 {
+        self.spent_at(player, now).periods_left
+    }
+
+    // 指し終えた手の消費時間の合計
+    #[wasm_bindgen]
+    pub fn get_consumed(&self, player: Player) -> f64 {
+        self.consumed[player as usize]
+    }
+}
+
+impl Clock {
+    fn elapsed_at(&self, now: f64) -> f64 {
+        self.elapsed + self.turn_started.map_or(0.0, |started| (now - started).max(0.0))
+    }
+
+    fn spent_at(&self, player: Player, now: f64) -> Spent {
+        let elapsed = if player == self.turn { self.elapsed_at(now) } else { 0.0 };
+        self.spend(player, elapsed)
+    }
+
+    // player の持ち時間・秒読みから elapsed を差し引く
+    fn spend(&self, player: Player, elapsed: f64) -> Spent {
+        let remaining = self.remaining[player as usize];
+        let mut periods_left = self.periods_left[player as usize];
+        if elapsed < remaining {
+            return Spent { remaining: remaining - elapsed, periods_left, byoyomi_used: 0.0, time_up: false };
+        }
+
+        let mut over = elapsed - remaining;
+        while periods_left > 0 && self.byoyomi > 0.0 && over >= self.byoyomi {
+            over -= self.byoyomi;
+            periods_left -= 1;
+        }
+        let time_up = periods_left == 0 || self.byoyomi <= 0.0;
+        Spent { remaining: 0.0, periods_left, byoyomi_used: over, time_up }
+    }
+}
+
+// KIF形式の消費時間（例: "( 0:16/00:01:23)"。1手の消費時間/その側の消費時間の合計。秒未満は切り捨て）
+#[wasm_bindgen]
+pub fn format_kif_time(move_time: f64, total_time: f64) -> String {
+    let move_seconds = (move_time.max(0.0) / 1000.0) as u64;
+    let total_seconds = (total_time.max(0.0) / 1000.0) as u64;
+    format!(
+        "({:>2}:{:02}/{:02}:{:02}:{:02})",
+        move_seconds / 60,
+        move_seconds % 60,
+        total_seconds / 3600,
+        total_seconds / 60 % 60,
+        total_seconds % 60
+    )
+}
@@ -0,0 +1,388 @@
+// 画像認識（OCR）で読み取った局面の補正
+//
+// 駒の枚数・二歩・行き所のない駒・玉の数の制約を満たすように、
+// できるだけ少ない修正で局面を直す候補を探す
+
+use std::collections::HashSet;
+
+use wasm_bindgen::prelude::*;
+
+use crate::bitboard::{position_of, Bitboard};
+use crate::movegen::HAND_PIECES;
+use crate::notation::{piece_name, player_name, square_name};
+use crate::validate::{IssueKind, ValidationIssue};
+use crate::{Board, Piece, Player};
+
+// 1つの候補に含める修正の最大数
+const MAX_CORRECTIONS: usize = 3;
+
+// 探索する局面数の上限
+const MAX_NODES: usize = 200_000;
+
+// 信頼度が与えられていないマスの信頼度
+const DEFAULT_CONFIDENCE: f64 = 1.0;
+
+// 駒を取り除く修正は読み違いより起こりにくいので重くする
+const REMOVAL_PENALTY: f64 = 0.5;
+
+// 持ち駒の枚数を直す修正のコスト
+const HAND_CORRECTION_COST: f64 = 2.0;
+
+// 修正1件
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct Correction {
+    row: i32,    // 持ち駒の修正の場合は-1
+    column: i32, // 持ち駒の修正の場合は-1
+    old_piece: Piece,
+    old_player: Player,
+    new_piece: Piece, // 駒を取り除く（持ち駒を1枚減らす）場合はEmpty
+    new_player: Player,
+    description: String,
+}
+
+#[wasm_bindgen]
+impl Correction {
+    #[wasm_bindgen]
+    pub fn get_row(&self) -> i32 {
+        self.row
+    }
+
+    #[wasm_bindgen]
+    pub fn get_column(&self) -> i32 {
+        self.column
+    }
+
+    #[wasm_bindgen]
+    pub fn is_hand(&self) -> bool {
+        self.row < 0
+    }
+
+    #[wasm_bindgen]
+    pub fn get_old_piece(&self) -> Piece {
+        self.old_piece
+    }
+
+    #[wasm_bindgen]
+    pub fn get_old_player(&self) -> Player {
+        self.old_player
+    }
+
+    #[wasm_bindgen]
+    pub fn get_new_piece(&self) -> Piece {
+        self.new_piece
+    }
+
+    #[wasm_bindgen]
+    pub fn get_new_player(&self) -> Player {
+        self.new_player
+    }
+
+    #[wasm_bindgen]
+    pub fn get_description(&self) -> String {
+        self.description.clone()
+    }
+}
+
+// 修正の組み合わせ1つと、修正後の局面
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct CorrectionCandidate {
+    corrections: Vec<Correction>,
+    cost: f64, // 小さいほど有力
+    board: Board,
+}
+
+#[wasm_bindgen]
+impl CorrectionCandidate {
+    #[wasm_bindgen]
+    pub fn get_corrections(&self) -> Vec<Correction> {
+        self.corrections.clone()
+    }
+
+    #[wasm_bindgen]
+    pub fn get_cost(&self) -> f64 {
+        self.cost
+    }
+
+    #[wasm_bindgen]
+    pub fn get_board(&self) -> Board {
+        self.board.clone()
+    }
+}
+
+// 探索中の修正
+#[derive(Clone, Copy, PartialEq)]
+enum Edit {
+    // マスの駒を置き換える（Emptyなら取り除く）
+    Square { sq: usize, piece: Piece, player: Player },
+    // 持ち駒を1枚減らす
+    Hand { player: Player, piece: Piece },
+}
+
+impl Edit {
+    // 修正の組み合わせを順序によらず比較するためのキー
+    fn key(self) -> (usize, usize, usize) {
+        match self {
+            Edit::Square { sq, piece, player } => (sq, piece as usize, player as usize),
+            Edit::Hand { player, piece } => (81 + player as usize, piece as usize, 0),
+        }
+    }
+}
+
+// 読み違えやすい駒
+fn confusable_pieces(piece: Piece) -> &'static [Piece] {
+    match piece {
+        Piece::Pawn => &[Piece::PromotedPawn, Piece::Lance],
+        Piece::Lance => &[Piece::PromotedLance, Piece::Pawn],
+        Piece::Knight => &[Piece::PromotedKnight],
+        Piece::Silver => &[Piece::PromotedSilver, Piece::Gold],
+        Piece::Gold => &[
+            Piece::PromotedSilver,
+            Piece::PromotedKnight,
+            Piece::PromotedLance,
+            Piece::PromotedPawn,
+            Piece::Silver,
+        ],
+        Piece::Bishop => &[Piece::PromotedBishop],
+        Piece::Rook => &[Piece::PromotedRook],
+        Piece::PromotedPawn => &[Piece::Pawn, Piece::Gold],
+        Piece::PromotedLance => &[Piece::Lance, Piece::Gold, Piece::PromotedKnight, Piece::PromotedSilver],
+        Piece::PromotedKnight => &[Piece::Knight, Piece::Gold, Piece::PromotedLance, Piece::PromotedSilver],
+        Piece::PromotedSilver => &[Piece::Silver, Piece::Gold, Piece::PromotedLance, Piece::PromotedKnight],
+        Piece::PromotedBishop => &[Piece::Bishop, Piece::PromotedRook],
+        Piece::PromotedRook => &[Piece::Rook, Piece::PromotedBishop],
+        Piece::King | Piece::Empty => &[],
+    }
+}
+
+struct Search<'a> {
+    confidence: &'a [f64],
+    nodes: usize,
+    seen: HashSet<Vec<(usize, usize, usize)>>,
+    results: Vec<(Vec<Edit>, f64, Board)>,
+}
+
+impl Search<'_> {
+    fn square_confidence(&self, sq: usize) -> f64 {
+        self.confidence.get(sq).copied().unwrap_or(DEFAULT_CONFIDENCE).clamp(0.0, 1.0)
+    }
+
+    fn edit_cost(&self, board: &Board, edit: Edit) -> f64 {
+        match edit {
+            Edit::Square { sq, piece, .. } => {
+                let base = 1.0 + self.square_confidence(sq);
+                if piece == Piece::Empty && board.pieces[sq].0 != Piece::Empty {
+                    base + REMOVAL_PENALTY
+                } else {
+                    base
+                }
+            }
+            Edit::Hand { .. } => HAND_CORRECTION_COST,
+        }
+    }
+
+    fn run(&mut self, board: &Board, edits: &mut Vec<Edit>, touched: Bitboard, cost: f64) {
+        self.nodes += 1;
+        if self.nodes > MAX_NODES {
+            return;
+        }
+
+        let issues: Vec<ValidationIssue> =
+            board.placement_issues().into_iter().filter(|issue| issue.is_error).collect();
+        let Some(issue) = issues.first() else {
+            let mut sorted = edits.clone();
+            sorted.sort_by_key(|edit| edit.key());
+            if self.seen.insert(sorted.iter().map(|edit| edit.key()).collect()) {
+                self.results.push((sorted, cost, board.clone()));
+            }
+            return;
+        };
+        if edits.len() == MAX_CORRECTIONS {
+            return;
+        }
+
+        // どの解もこの問題を解消しているはずなので、この問題に関係する修正だけを試す
+        for edit in board.candidate_edits(issue, touched) {
+            let mut next = board.clone();
+            next.apply_edit(edit);
+            let mut next_touched = touched;
+            if let Edit::Square { sq, .. } = edit {
+                next_touched.set(sq);
+            }
+            let edit_cost = self.edit_cost(board, edit);
+            edits.push(edit);
+            self.run(&next, edits, next_touched, cost + edit_cost);
+            edits.pop();
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Board {
+    // 読み取った局面の修正候補を有力な順に返す
+    // confidence はマス番号（段 * 9 + 筋）ごとの認識の信頼度（0〜1）。空の場合はすべて同じとみなす
+    // 局面に問題がなければ修正なしの候補1つだけを返す
+    #[wasm_bindgen]
+    pub fn suggest_corrections(&self, confidence: Vec<f64>, max_results: usize) -> Vec<CorrectionCandidate> {
+        let mut search = Search {
+            confidence: &confidence,
+            nodes: 0,
+            seen: HashSet::new(),
+            results: Vec::new(),
+        };
+        search.run(self, &mut Vec::new(), Bitboard::EMPTY, 0.0);
+
+        // 他の候補の修正をすべて含む候補は最小ではないので除く
+        let all: Vec<Vec<Edit>> = search.results.iter().map(|(edits, _, _)| edits.clone()).collect();
+        let mut results: Vec<(Vec<Edit>, f64, Board)> = search
+            .results
+            .into_iter()
+            .filter(|(edits, _, _)| {
+                !all.iter().any(|other| {
+                    other.len() < edits.len() && other.iter().all(|edit| edits.contains(edit))
+                })
+            })
+            .collect();
+        results.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.len().cmp(&b.0.len())));
+        results.truncate(max_results);
+
+        results
+            .into_iter()
+            .map(|(edits, cost, board)| CorrectionCandidate {
+                corrections: edits.iter().map(|&edit| self.describe_edit(edit)).collect(),
+                cost,
+                board,
+            })
+            .collect()
+    }
+}
+
+impl Board {
+    // 問題を解消しうる修正の一覧（修正済みのマスは再度変更しない）
+    fn candidate_edits(&self, issue: &ValidationIssue, touched: Bitboard) -> Vec<Edit> {
+        let mut edits = Vec::new();
+        match issue.kind {
+            IssueKind::KingCount => {
+                // 同じ側に玉が2枚以上ある場合は、余分な玉の先後の読み違いか誤検出。
+                // どれが余分かはわからないので、すべての玉について反転と取り除きを試す
+                for player in [Player::Black, Player::White] {
+                    let kings = self.pieces_of(Piece::King, player);
+                    if kings.count() > 1 {
+                        for sq in kings & !touched {
+                            edits.push(Edit::Square { sq, piece: Piece::King, player: player.opponent() });
+                            edits.push(Edit::Square { sq, piece: Piece::Empty, player: Player::Black });
+                        }
+                    }
+                }
+            }
+            IssueKind::PieceCountExceeded => {
+                let family = issue.piece;
+                let mut squares = self.by_piece[family as usize];
+                if let Some(promoted) = self.get_promoted_piece(family) {
+                    squares |= self.by_piece[promoted as usize];
+                }
+                for sq in squares & !touched {
+                    for edit in self.square_alternatives(sq) {
+                        // 同じ種類の駒のままでは枚数が減らない
+                        if let Edit::Square { piece, .. } = edit {
+                            if piece == Piece::Empty || self.get_original_piece(piece) != family {
+                                edits.push(edit);
+                            }
+                        }
+                    }
+                }
+                for player in [Player::Black, Player::White] {
+                    if self.get_captured_piece_count(player, family) > 0 {
+                        edits.push(Edit::Hand { player, piece: family });
+                    }
+                }
+            }
+            IssueKind::TooManyPieces => {
+                for sq in self.occupied() & !touched & !self.by_piece[Piece::King as usize] {
+                    edits.push(Edit::Square { sq, piece: Piece::Empty, player: Player::Black });
+                }
+                for player in [Player::Black, Player::White] {
+                    for piece in HAND_PIECES {
+                        if self.get_captured_piece_count(player, piece) > 0 {
+                            edits.push(Edit::Hand { player, piece });
+                        }
+                    }
+                }
+            }
+            IssueKind::DoublePawn => {
+                // 同じ筋の歩のどれが読み違いかはわからない
+                let sq = (issue.row * 9 + issue.column) as usize;
+                let (_, player) = self.pieces[sq];
+                let pawns = self.pieces_of(Piece::Pawn, player) & Bitboard::file(issue.column as usize);
+                for pawn in pawns & !touched {
+                    edits.extend(self.square_alternatives(pawn));
+                }
+            }
+            IssueKind::DeadPiece => {
+                let sq = (issue.row * 9 + issue.column) as usize;
+                if !touched.has(sq) {
+                    edits.extend(self.square_alternatives(sq));
+                }
+            }
+            IssueKind::OpponentInCheck | IssueKind::BothKingsInCheck => {}
+        }
+        edits
+    }
+
+    // マスの駒の読み違いとして考えられるもの（先後の反転、似た駒、誤検出）
+    fn square_alternatives(&self, sq: usize) -> Vec<Edit> {
+        let (piece, player) = self.pieces[sq];
+        let mut edits = vec![Edit::Square { sq, piece, player: player.opponent() }];
+        for &alternative in confusable_pieces(piece) {
+            edits.push(Edit::Square { sq, piece: alternative, player });
+        }
+        if piece != Piece::King {
+            edits.push(Edit::Square { sq, piece: Piece::Empty, player: Player::Black });
+        }
+        edits
+    }
+
+    fn apply_edit(&mut self, edit: Edit) {
+        match edit {
+            Edit::Square { sq, piece, player } => self.put_piece(sq, piece, player),
+            Edit::Hand { player, piece } => {
+                let count = self.get_captured_piece_count(player, piece);
+                self.set_captured_piece_count(player, piece, count - 1);
+            }
+        }
+    }
+
+    fn describe_edit(&self, edit: Edit) -> Correction {
+        match edit {
+            Edit::Square { sq, piece, player } => {
+                let (old_piece, old_player) = self.pieces[sq];
+                let pos = position_of(sq);
+                let before = format!("{}{}", player_name(old_player), piece_name(old_piece));
+                let description = if piece == Piece::Empty {
+                    format!("{}の{}を取り除く", square_name(sq), before)
+                } else {
+                    format!("{}の{}を{}{}にする", square_name(sq), before, player_name(player), piece_name(piece))
+                };
+                Correction {
+                    row: pos.row,
+                    column: pos.column,
+                    old_piece,
+                    old_player,
+                    new_piece: piece,
+                    new_player: player,
+                    description,
+                }
+            }
+            Edit::Hand { player, piece } => Correction {
+                row: -1,
+                column: -1,
+                old_piece: piece,
+                old_player: player,
+                new_piece: Piece::Empty,
+                new_player: player,
+                description: format!("{}の持ち駒の{}を1枚減らす", player_name(player), piece_name(piece)),
+            },
+        }
+    }
+}
@@ -0,0 +1,83 @@
+// 入玉宣言（相入玉で盤上では終わらない対局を、点数で勝ち・持将棋にする）
+//
+// 点数は大駒（角・飛、成った馬・龍を含む）5点、小駒1点で、玉を除いた敵陣の駒と持ち駒を数える。
+// 対局時計を使っている場合は declare_win_on_clock で持ち時間が残っていることも確認する
+
+use wasm_bindgen::prelude::*;
+
+use crate::bitboard::Bitboard;
+use crate::movegen::HAND_PIECES;
+use crate::{Board, Clock, Piece, Player};
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq)]
+pub enum DeclarationRule {
+    Points24, // 24点法（31点以上で勝ち、24〜30点は持将棋）
+    Points27, // 27点法（先手28点・後手27点以上で勝ち）
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq)]
+pub enum DeclarationResult {
+    Win,
+    Draw,    // 持将棋（24点法で24〜30点）
+    Invalid, // 宣言の条件を満たしていない（宣言した側の負け）
+}
+
+fn piece_points(piece: Piece) -> i32 {
+    match piece {
+        Piece::Bishop | Piece::Rook | Piece::PromotedBishop | Piece::PromotedRook => 5,
+        Piece::King | Piece::Empty => 0,
+        _ => 1,
+    }
+}
+
+#[wasm_bindgen]
+impl Board {
+    // player の宣言の点数（敵陣にある玉以外の駒と持ち駒）
+    #[wasm_bindgen]
+    pub fn declaration_points(&self, player: Player) -> i32 {
+        let on_board: i32 = self.pieces_in_enemy_camp(player).map(|sq| piece_points(self.pieces[sq].0)).sum();
+        let in_hand: i32 = HAND_PIECES
+            .iter()
+            .map(|&piece| piece_points(piece) * self.get_captured_piece_count(player, piece))
+            .sum();
+        on_board + in_hand
+    }
+
+    // 手番の側が入玉宣言をした結果
+    // 玉が敵陣にいる・玉以外の駒が敵陣に10枚以上ある・王手されていない、を満たした上で点数で決まる
+    #[wasm_bindgen]
+    pub fn declare_win(&self, rule: DeclarationRule) -> DeclarationResult {
+        let player = self.current_player;
+        let camp = Bitboard::promotion_zone(player);
+        let king_entered = self.king_square(player).is_some_and(|king| camp.has(king));
+        if !king_entered || self.pieces_in_enemy_camp(player).count() < 10 || self.is_in_check(player) {
+            return DeclarationResult::Invalid;
+        }
+
+        let points = self.declaration_points(player);
+        match rule {
+            DeclarationRule::Points24 if points >= 31 => DeclarationResult::Win,
+            DeclarationRule::Points24 if points >= 24 => DeclarationResult::Draw,
+            DeclarationRule::Points27 if points >= if player == Player::Black { 28 } else { 27 } => DeclarationResult::Win,
+            _ => DeclarationResult::Invalid,
+        }
+    }
+
+    // 対局時計を使っている対局での入玉宣言（now の時点で手番の側が時間切れなら宣言できない）
+    #[wasm_bindgen]
+    pub fn declare_win_on_clock(&self, rule: DeclarationRule, clock: &Clock, now: f64) -> DeclarationResult {
+        if clock.is_time_up(self.current_player, now) {
+            return DeclarationResult::Invalid;
+        }
+        self.declare_win(rule)
+    }
+}
+
+impl Board {
+    // player 側の、敵陣にある玉以外の駒
+    fn pieces_in_enemy_camp(&self, player: Player) -> Bitboard {
+        self.by_player[player as usize] & Bitboard::promotion_zone(player) & !self.pieces_of(Piece::King, player)
+    }
+}
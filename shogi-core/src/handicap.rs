@@ -0,0 +1,134 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{Board, Player, Position};
+
+// 駒落ちの種類（上手＝後手の駒を落とし、上手から指し始める）
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq)]
+pub enum Handicap {
+    Even,          // 平手
+    Lance,         // 香落ち
+    RightLance,    // 右香落ち
+    Bishop,        // 角落ち
+    Rook,          // 飛車落ち
+    RookLance,     // 飛香落ち
+    TwoPieces,     // 二枚落ち
+    FourPieces,    // 四枚落ち
+    SixPieces,     // 六枚落ち
+    EightPieces,   // 八枚落ち
+    TenPieces,     // 十枚落ち
+}
+
+pub(crate) const ALL_HANDICAPS: [Handicap; 11] = [
+    Handicap::Even,
+    Handicap::Lance,
+    Handicap::RightLance,
+    Handicap::Bishop,
+    Handicap::Rook,
+    Handicap::RookLance,
+    Handicap::TwoPieces,
+    Handicap::FourPieces,
+    Handicap::SixPieces,
+    Handicap::EightPieces,
+    Handicap::TenPieces,
+];
+
+impl Handicap {
+    // KIFの「手合割」に書く名前
+    pub fn kif_name(self) -> &'static str {
+        match self {
+            Handicap::Even => "平手",
+            Handicap::Lance => "香落ち",
+            Handicap::RightLance => "右香落ち",
+            Handicap::Bishop => "角落ち",
+            Handicap::Rook => "飛車落ち",
+            Handicap::RookLance => "飛香落ち",
+            Handicap::TwoPieces => "二枚落ち",
+            Handicap::FourPieces => "四枚落ち",
+            Handicap::SixPieces => "六枚落ち",
+            Handicap::EightPieces => "八枚落ち",
+            Handicap::TenPieces => "十枚落ち",
+        }
+    }
+
+    pub fn from_kif_name(name: &str) -> Option<Handicap> {
+        ALL_HANDICAPS.iter().copied().find(|h| h.kif_name() == name.trim())
+    }
+
+    // 落とす駒のCSA表記（マスは筋・段の順、例: "82HI"）
+    fn removed_pieces(self) -> &'static [&'static str] {
+        match self {
+            Handicap::Even => &[],
+            Handicap::Lance => &["11KY"],
+            Handicap::RightLance => &["91KY"],
+            Handicap::Bishop => &["22KA"],
+            Handicap::Rook => &["82HI"],
+            Handicap::RookLance => &["82HI", "11KY"],
+            Handicap::TwoPieces => &["82HI", "22KA"],
+            Handicap::FourPieces => &["82HI", "22KA", "11KY", "91KY"],
+            Handicap::SixPieces => &["82HI", "22KA", "11KY", "91KY", "21KE", "81KE"],
+            Handicap::EightPieces => &["82HI", "22KA", "11KY", "91KY", "21KE", "81KE", "31GI", "71GI"],
+            Handicap::TenPieces => &[
+                "82HI", "22KA", "11KY", "91KY", "21KE", "81KE", "31GI", "71GI", "41KI", "61KI",
+            ],
+        }
+    }
+
+    // CSAの開始局面の指定（"PI"行と先に指す側の行）
+    pub fn csa_header(self) -> String {
+        let turn = if self == Handicap::Even { "+" } else { "-" };
+        format!("PI{}\n{}", self.removed_pieces().concat(), turn)
+    }
+
+    // CSAの"PI"行から駒落ちの種類を判定する
+    pub fn from_csa_pi(line: &str) -> Option<Handicap> {
+        let removed = line.trim().strip_prefix("PI")?;
+        // 4バイトずつ区切るので、全角文字などが混ざっている場合や半端な長さの場合は判定しない
+        if !removed.is_ascii() || removed.len() % 4 != 0 {
+            return None;
+        }
+        let mut removed: Vec<&str> = (0..removed.len() / 4).map(|i| &removed[i * 4..i * 4 + 4]).collect();
+        removed.sort_unstable();
+        ALL_HANDICAPS.iter().copied().find(|h| {
+            let mut pieces = h.removed_pieces().to_vec();
+            pieces.sort_unstable();
+            pieces == removed
+        })
+    }
+
+    pub fn first_player(self) -> Player {
+        if self == Handicap::Even {
+            Player::Black
+        } else {
+            Player::White
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Board {
+    // 駒落ちの初期局面を作成する
+    #[wasm_bindgen]
+    pub fn from_handicap(kind: Handicap) -> Board {
+        let mut board = Board::new();
+        for removed in kind.removed_pieces() {
+            let file = (removed.as_bytes()[0] - b'0') as i32;
+            let rank = (removed.as_bytes()[1] - b'0') as i32;
+            board.clear_square(Position::new(9 - rank, 9 - file));
+        }
+        board.set_current_player(kind.first_player());
+        board
+    }
+}
+
+// KIFの「手合割：」行に書く名前
+#[wasm_bindgen]
+pub fn handicap_kif_name(kind: Handicap) -> String {
+    kind.kif_name().to_string()
+}
+
+// CSAの"PI"行と手番の行
+#[wasm_bindgen]
+pub fn handicap_csa_header(kind: Handicap) -> String {
+    kind.csa_header()
+}
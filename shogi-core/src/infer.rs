@@ -0,0 +1,87 @@
+// 2つの局面の間に指された手順の推定（写真から棋譜を作るために使う）
+
+use wasm_bindgen::prelude::*;
+
+use crate::movegen::HAND_PIECES;
+use crate::{Board, Move, Player};
+
+// 読み取った局面に許す認識誤り（マスまたは持ち駒1枚）の数
+const MAX_FRAME_ERRORS: u32 = 1;
+
+// 推定した手順1つ
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct InferredMoves {
+    moves: Vec<Move>,
+    mismatches: u32, // 手順を指した局面と読み取った局面の違い（認識誤りとみなした数）
+}
+
+#[wasm_bindgen]
+impl InferredMoves {
+    #[wasm_bindgen]
+    pub fn get_moves(&self) -> Vec<Move> {
+        self.moves.clone()
+    }
+
+    #[wasm_bindgen]
+    pub fn get_mismatches(&self) -> u32 {
+        self.mismatches
+    }
+}
+
+// before から after に至る max_plies 手以内の合法な手順をすべて返す
+// after の認識誤りは1か所まで許し、違いの少ない順・手数の少ない順に並べる
+// 写真からは手番がわからないため、after の手番は比較しない
+//
+// before は正しい局面として扱い、手番も before のものを使う（before と after で誤りの扱いが違う）。
+// before の誤りも、手順で動かない駒のマスであれば after との違い1か所として許されるが、
+// 動かした駒のマスや手番を読み違えていると、その手が合法手にないため推定できない
+#[wasm_bindgen]
+pub fn infer_moves(before: &Board, after: &Board, max_plies: usize) -> Vec<InferredMoves> {
+    let mut board = before.clone();
+    let mut results = Vec::new();
+    let mut moves = Vec::new();
+    search(&mut board, after, max_plies, &mut moves, &mut results);
+    results.sort_by_key(|result| (result.mismatches, result.moves.len()));
+    results
+}
+
+fn search(board: &mut Board, target: &Board, remaining: usize, moves: &mut Vec<Move>, results: &mut Vec<InferredMoves>) {
+    let (squares, hands) = difference(board, target);
+    if !moves.is_empty() && squares + hands <= MAX_FRAME_ERRORS {
+        results.push(InferredMoves { moves: moves.clone(), mismatches: squares + hands });
+    }
+    // 1手で変わるのはたかだか2マスと持ち駒1枚なので、残りの手数で届かなければ打ち切る
+    if remaining == 0
+        || squares > 2 * remaining as u32 + MAX_FRAME_ERRORS
+        || hands > remaining as u32 + MAX_FRAME_ERRORS
+    {
+        return;
+    }
+
+    for mv in board.generate_legal_moves() {
+        let undo = board.do_move(mv);
+        moves.push(mv);
+        search(board, target, remaining - 1, moves, results);
+        moves.pop();
+        board.undo_move(mv, undo);
+    }
+}
+
+// 駒の配置が違うマスの数と、持ち駒の枚数の違いの合計
+fn difference(a: &Board, b: &Board) -> (u32, u32) {
+    let squares = a
+        .pieces
+        .iter()
+        .zip(b.pieces.iter())
+        .filter(|(x, y)| x != y)
+        .count() as u32;
+    let mut hands = 0;
+    for player in [Player::Black, Player::White] {
+        for piece in HAND_PIECES {
+            let diff = a.get_captured_piece_count(player, piece) - b.get_captured_piece_count(player, piece);
+            hands += diff.unsigned_abs();
+        }
+    }
+    (squares, hands)
+}
@@ -1,7 +1,58 @@
 use wasm_bindgen::prelude::*;
 
+mod attack;
+mod bitboard;
+mod bod;
+mod castle;
+mod clock;
+mod correction;
+mod declaration;
+mod handicap;
+mod infer;
+mod makemove;
+mod movegen;
+mod movetext;
+mod notation;
+mod ocr_eval;
+mod outcome;
+mod perft;
+mod piecebox;
+mod pin;
+mod see;
+#[cfg(feature = "serde")]
+mod serialize;
+mod sfen;
+mod status;
+mod svg;
+mod synth;
+mod termination;
+mod validate;
+mod zobrist;
+
+use bitboard::{square, square_of, Bitboard};
+
+pub use castle::CastleMatch;
+pub use clock::{format_kif_time, Clock};
+pub use correction::{Correction, CorrectionCandidate};
+pub use declaration::{DeclarationResult, DeclarationRule};
+pub use handicap::Handicap;
+pub use infer::{infer_moves, InferredMoves};
+pub use makemove::UndoInfo;
+pub use movetext::MoveTextResult;
+pub use ocr_eval::OcrEvaluation;
+pub use outcome::MoveOutcome;
+pub use pin::BlockedLine;
+pub use status::GameStatus;
+pub use svg::{PieceStyle, SvgOptions};
+// データ生成ツール（ocr_dataset）と同じ乱数を使うため
+#[cfg(feature = "datagen")]
+pub use synth::Rng;
+pub use termination::{GameResult, Termination};
+pub use validate::{IssueKind, ValidationIssue};
+
 #[wasm_bindgen]
 #[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "snake_case"))]
 pub enum Piece {
     Empty,
     Pawn,    // 歩
@@ -22,13 +73,25 @@ pub enum Piece {
 
 #[wasm_bindgen]
 #[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "snake_case"))]
 pub enum Player {
     Black, // 先手
     White, // 後手
 }
 
+impl Player {
+    // 相手側のプレイヤー
+    pub(crate) fn opponent(self) -> Player {
+        match self {
+            Player::Black => Player::White,
+            Player::White => Player::Black,
+        }
+    }
+}
+
 #[wasm_bindgen]
 #[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Position {
     pub row: i32,    // 1-9
     pub column: i32, // 1-9
@@ -39,7 +102,7 @@ impl Position {
     #[wasm_bindgen(constructor)]
     pub fn new(row: i32, column: i32) -> Position {
         // 入力値の検証
-        if row < 0 || row >= 9 || column < 0 || column >= 9 {
+        if !(0..9).contains(&row) || !(0..9).contains(&column) {
             // デフォルト値として(0, 0)を返す
             Position { row: 0, column: 0 }
         } else {
@@ -48,7 +111,7 @@ impl Position {
     }
 
     fn is_valid(&self) -> bool {
-        self.row >= 0 && self.row < 9 && self.column >= 0 && self.column < 9
+        (0..9).contains(&self.row) && (0..9).contains(&self.column)
     }
 
     // デバッグ用のメソッド
@@ -76,12 +139,94 @@ pub struct PieceInfo {
     pub player: Player,
 }
 
+// 指し手（盤上の駒の移動または持ち駒を打つ手）
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Move {
+    from: Position, // 持ち駒を打つ場合は(-1, -1)
+    to: Position,
+    piece: Piece,   // 動かす前の駒（打つ場合は打つ駒）
+    promote: bool,
+}
+
+#[wasm_bindgen]
+impl Move {
+    #[wasm_bindgen(constructor)]
+    pub fn new(from: Position, to: Position, piece: Piece, promote: bool) -> Move {
+        Move { from, to, piece, promote }
+    }
+
+    // 持ち駒を打つ手を作成する
+    #[wasm_bindgen]
+    pub fn new_drop(piece: Piece, to: Position) -> Move {
+        Move {
+            from: Position { row: -1, column: -1 },
+            to,
+            piece,
+            promote: false,
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn get_from(&self) -> Position {
+        self.from
+    }
+
+    #[wasm_bindgen]
+    pub fn get_to(&self) -> Position {
+        self.to
+    }
+
+    #[wasm_bindgen]
+    pub fn get_piece(&self) -> Piece {
+        self.piece
+    }
+
+    #[wasm_bindgen]
+    pub fn is_promotion(&self) -> bool {
+        self.promote
+    }
+
+    #[wasm_bindgen]
+    pub fn is_drop(&self) -> bool {
+        self.from.row < 0
+    }
+
+    // デバッグ用のメソッド
+    #[wasm_bindgen]
+    pub fn debug_info(&self) -> String {
+        if self.is_drop() {
+            format!("Move(drop -> ({}, {}))", self.to.row, self.to.column)
+        } else {
+            format!(
+                "Move(({}, {}) -> ({}, {}){})",
+                self.from.row,
+                self.from.column,
+                self.to.row,
+                self.to.column,
+                if self.promote { " +" } else { "" }
+            )
+        }
+    }
+}
+
 #[wasm_bindgen]
 #[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "serialize::BoardRecord", try_from = "serialize::BoardRecord")
+)]
 pub struct Board {
-    pieces: [[(Piece, Player); 9]; 9],
+    pieces: [(Piece, Player); 81], // マス番号（段 * 9 + 筋）ごとの駒
+    by_player: [Bitboard; 2],      // [player] その手番の駒があるマス
+    by_piece: [Bitboard; 15],      // [piece] その種類の駒があるマス（先後両方）
     current_player: Player,
-    captured_pieces: [[i32; 8]; 2], // [player][piece_type] で持ち駒の数を管理
+    captured_pieces: [[i32; 7]; 2], // [player][piece_type] で持ち駒の数を管理（玉は持ち駒にならない）
+    captured_king: Option<Player>,  // 玉が取られた場合、その玉の持ち主（対局終了）
+    try_rule: bool,                 // トライルールで対局するか（局面ではなく対局の設定）
+    hash: u64,                      // 盤面・持ち駒・手番から計算するハッシュ値（差分更新する）
 }
 
 #[wasm_bindgen]
@@ -89,9 +234,14 @@ impl Board {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Board {
         let mut board = Board {
-            pieces: [[(Piece::Empty, Player::Black); 9]; 9],
+            pieces: [(Piece::Empty, Player::Black); 81],
+            by_player: [Bitboard::EMPTY; 2],
+            by_piece: [Bitboard::EMPTY; 15],
             current_player: Player::Black,
-            captured_pieces: [[0; 8]; 2],
+            captured_pieces: [[0; 7]; 2],
+            captured_king: None,
+            try_rule: false,
+            hash: 0,
         };
         board.initialize();
         board
@@ -105,94 +255,22 @@ impl Board {
         }
     }
 
+    // from の駒を to に動かす合法手があるか（自玉に王手がかかったままになる手は含まない）
     #[wasm_bindgen]
     pub fn is_valid_move(&self, from: Position, to: Position) -> bool {
-        // 1. 移動元に駒があるか
-        let (piece, player) = match self.get_piece_at(from) {
-            Some(p) => p,
-            None => return false,
-        };
-
-        // 2. その駒が現在のプレイヤーのものか
-        if player != self.current_player {
-            return false;
-        }
-
-        // 3. 移動先が盤面内か
-        if !self.is_valid_position(to) {
-            return false;
-        }
-
-        // 4. 移動先に味方の駒がないか
-        if !self.is_empty_or_opponent(to, player) {
-            return false;
-        }
-
-        // 5. その駒の動きとして合法か
-        let valid_moves = self.get_piece_moves(from, piece, player);
-        valid_moves.contains(&to)
+        self.get_valid_moves(from).contains(&to)
     }
 
     #[wasm_bindgen]
     pub fn make_move(&mut self, from: Position, to: Position) -> bool {
-        if !self.is_valid_move(from, to) {
-            return false;
-        }
-
-        let (piece, player) = self.pieces[from.row as usize][from.column as usize];
-        
-        // 移動先に相手の駒がある場合は持ち駒に追加
-        if let Some((captured_piece, captured_player)) = self.get_piece_at(to) {
-            if captured_player != player {
-                // 成り駒は元の駒に戻して持ち駒に追加
-                let original_piece = self.get_original_piece(captured_piece);
-                self.add_captured_piece(player, original_piece);
-            }
-        }
-        
         // 自動成りを無効化 - 駒をそのまま移動
-        self.pieces[to.row as usize][to.column as usize] = (piece, player);
-        self.pieces[from.row as usize][from.column as usize] = (Piece::Empty, Player::Black);
-        self.current_player = if self.current_player == Player::Black {
-            Player::White
-        } else {
-            Player::Black
-        };
-        true
+        self.make_move_with_promotion(from, to, false)
     }
 
     #[wasm_bindgen]
     pub fn make_move_with_promotion(&mut self, from: Position, to: Position, promote: bool) -> bool {
-        if !self.is_valid_move(from, to) {
-            return false;
-        }
-
-        let (piece, player) = self.pieces[from.row as usize][from.column as usize];
-        
-        // 移動先に相手の駒がある場合は持ち駒に追加
-        if let Some((captured_piece, captured_player)) = self.get_piece_at(to) {
-            if captured_player != player {
-                // 成り駒は元の駒に戻して持ち駒に追加
-                let original_piece = self.get_original_piece(captured_piece);
-                self.add_captured_piece(player, original_piece);
-            }
-        }
-        
-        // 成り判定を手動で行う
-        let final_piece = if promote && self.can_promote(from.row, from.column, to.row, to.column) {
-            self.get_promoted_piece(piece).unwrap_or(piece)
-        } else {
-            piece
-        };
-
-        self.pieces[to.row as usize][to.column as usize] = (final_piece, player);
-        self.pieces[from.row as usize][from.column as usize] = (Piece::Empty, Player::Black);
-        self.current_player = if self.current_player == Player::Black {
-            Player::White
-        } else {
-            Player::Black
-        };
-        true
+        // 成れない手で promote が true の場合は成らずに動かす
+        self.make_move_with_outcome(from, to, promote).is_some()
     }
 
     #[wasm_bindgen]
@@ -200,39 +278,27 @@ impl Board {
         self.current_player
     }
 
+    // from の駒の合法な移動先（成る・成らないは区別しない）
     #[wasm_bindgen]
     pub fn get_valid_moves(&self, from: Position) -> Vec<Position> {
-        let (piece, player) = match self.get_piece_at(from) {
-            Some(p) => p,
-            None => return Vec::new(),
-        };
-
-        if player != self.current_player {
+        if self.get_piece_at(from).is_none() {
             return Vec::new();
         }
-
-        self.get_piece_moves(from, piece, player)
-            .into_iter()
-            .filter(|&to| self.is_empty_or_opponent(to, player))
-            .collect()
+        let mut destinations: Vec<Position> = Vec::new();
+        for mv in self.generate_legal_moves() {
+            if !mv.is_drop() && mv.from == from && !destinations.contains(&mv.to) {
+                destinations.push(mv.to);
+            }
+        }
+        destinations
     }
 
     #[wasm_bindgen]
     pub fn get_valid_moves_by_coords(&self, from_row: i32, from_col: i32) -> Vec<Position> {
-        let from = Position::new(from_row, from_col);
-        let (piece, player) = match self.get_piece_at(from) {
-            Some(p) => p,
-            None => return Vec::new(),
-        };
-
-        if player != self.current_player {
+        if !(0..9).contains(&from_row) || !(0..9).contains(&from_col) {
             return Vec::new();
         }
-
-        self.get_piece_moves(from, piece, player)
-            .into_iter()
-            .filter(|&to| self.is_empty_or_opponent(to, player))
-            .collect()
+        self.get_valid_moves(Position::new(from_row, from_col))
     }
 
     #[wasm_bindgen]
@@ -279,8 +345,8 @@ impl Board {
         // 後ろ方向に動ける駒（銀、角、飛車）の場合は、移動元が敵陣にあるか、移動先が敵陣に入るか、移動先が敵陣から出る場合に成れる
         if self.can_move_backward(piece) {
             match player {
-                Player::Black => from.row >= 6 || to.row >= 6 || (from.row >= 6 && to.row < 6), // 移動元が敵陣にあるか、移動先が敵陣に入るか、敵陣から出る
-                Player::White => from.row <= 2 || to.row <= 2 || (from.row <= 2 && to.row > 2), // 移動元が敵陣にあるか、移動先が敵陣に入るか、敵陣から出る
+                Player::Black => from.row >= 6 || to.row >= 6, // 移動元が敵陣にあるか、移動先が敵陣に入るか、敵陣から出る
+                Player::White => from.row <= 2 || to.row <= 2, // 移動元が敵陣にあるか、移動先が敵陣に入るか、敵陣から出る
             }
         } else {
             // 後ろ方向に動けない駒（歩、香車、桂馬）は従来通り敵陣に入ったタイミングのみ
@@ -291,13 +357,11 @@ impl Board {
         }
     }
 
+    // JS側に公開するためのメソッド名なのでClone::cloneとは別に定義する
     #[wasm_bindgen]
+    #[allow(clippy::should_implement_trait)]
     pub fn clone(&self) -> Board {
-        Board {
-            pieces: self.pieces,
-            current_player: self.current_player,
-            captured_pieces: self.captured_pieces,
-        }
+        Clone::clone(self)
     }
 
     #[wasm_bindgen]
@@ -305,7 +369,7 @@ impl Board {
         if !position.is_valid() {
             return false;
         }
-        self.pieces[position.row as usize][position.column as usize] = (piece, player);
+        self.put_piece(square_of(position), piece, player);
         true
     }
 
@@ -320,7 +384,7 @@ impl Board {
         if !position.is_valid() {
             return false;
         }
-        self.pieces[position.row as usize][position.column as usize] = (Piece::Empty, Player::Black);
+        self.remove_piece(square_of(position));
         true
     }
 
@@ -332,31 +396,45 @@ impl Board {
 
     #[wasm_bindgen]
     pub fn clear_board(&mut self) {
-        for row in 0..9 {
-            for col in 0..9 {
-                self.pieces[row][col] = (Piece::Empty, Player::Black);
-            }
+        for sq in self.occupied() {
+            self.remove_piece(sq);
         }
+        self.captured_king = None;
     }
 
     #[wasm_bindgen]
     pub fn set_current_player(&mut self, player: Player) {
-        self.current_player = player;
+        if self.current_player != player {
+            self.switch_player();
+        }
     }
 
     #[wasm_bindgen]
     pub fn reset_to_initial_position(&mut self) {
         self.clear_board();
-        self.current_player = Player::Black;
-        self.captured_pieces = [[0; 8]; 2];
+        self.set_current_player(Player::Black);
+        self.clear_captured_pieces(Player::Black);
+        self.clear_captured_pieces(Player::White);
         self.initialize();
     }
 
+    // 玉が取られて対局が終了しているか（取られた側が手番になっている）
+    #[wasm_bindgen]
+    pub fn is_king_captured(&self) -> bool {
+        self.captured_king.is_some()
+    }
+
+    // 局面のハッシュ値（同一局面の判定に使う）
+    #[wasm_bindgen]
+    pub fn get_hash(&self) -> u64 {
+        self.hash
+    }
+
     #[wasm_bindgen]
     pub fn get_captured_piece_count(&self, player: Player, piece: Piece) -> i32 {
         let player_index = if player == Player::Black { 0 } else { 1 };
         let piece_index = self.piece_to_index(piece);
-        if piece_index >= 0 && piece_index < 8 {
+        if (0..7).contains(&piece_index) {
             self.captured_pieces[player_index][piece_index as usize]
         } else {
             0
@@ -367,8 +445,9 @@ impl Board {
     pub fn add_captured_piece(&mut self, player: Player, piece: Piece) {
         let player_index = if player == Player::Black { 0 } else { 1 };
         let piece_index = self.piece_to_index(piece);
-        if piece_index >= 0 && piece_index < 8 {
-            self.captured_pieces[player_index][piece_index as usize] += 1;
+        if (0..7).contains(&piece_index) {
+            let count = self.captured_pieces[player_index][piece_index as usize];
+            self.set_hand_count(player_index, piece_index as usize, count + 1);
         }
     }
 
@@ -376,9 +455,10 @@ impl Board {
     pub fn use_captured_piece(&mut self, player: Player, piece: Piece) -> bool {
         let player_index = if player == Player::Black { 0 } else { 1 };
         let piece_index = self.piece_to_index(piece);
-        if piece_index >= 0 && piece_index < 8 {
-            if self.captured_pieces[player_index][piece_index as usize] > 0 {
-                self.captured_pieces[player_index][piece_index as usize] -= 1;
+        if (0..7).contains(&piece_index) {
+            let count = self.captured_pieces[player_index][piece_index as usize];
+            if count > 0 {
+                self.set_hand_count(player_index, piece_index as usize, count - 1);
                 true
             } else {
                 false
@@ -392,8 +472,8 @@ impl Board {
     pub fn set_captured_piece_count(&mut self, player: Player, piece: Piece, count: i32) -> bool {
         let player_index = if player == Player::Black { 0 } else { 1 };
         let piece_index = self.piece_to_index(piece);
-        if piece_index >= 0 && piece_index < 8 && count >= 0 {
-            self.captured_pieces[player_index][piece_index as usize] = count;
+        if (0..7).contains(&piece_index) && count >= 0 {
+            self.set_hand_count(player_index, piece_index as usize, count);
             true
         } else {
             false
@@ -403,8 +483,8 @@ impl Board {
     #[wasm_bindgen]
     pub fn clear_captured_pieces(&mut self, player: Player) {
         let player_index = if player == Player::Black { 0 } else { 1 };
-        for i in 0..8 {
-            self.captured_pieces[player_index][i] = 0;
+        for i in 0..7 {
+            self.set_hand_count(player_index, i, 0);
         }
     }
 
@@ -413,57 +493,21 @@ impl Board {
         let mut result = Vec::new();
         for player in [Player::Black, Player::White] {
             let player_index = if player == Player::Black { 0 } else { 1 };
-            for piece_index in 0..8 {
+            for piece_index in 0..7 {
                 result.push(self.captured_pieces[player_index][piece_index]);
             }
         }
         result
     }
 
+    // 持ち駒を打つ合法手か（二歩・行き所のない駒・打ち歩詰め・王手を放置する手は打てない）
     #[wasm_bindgen]
     pub fn can_drop_piece(&self, piece: Piece, to_row: i32, to_col: i32) -> bool {
-        // 持ち駒があるかチェック
-        if self.get_captured_piece_count(self.current_player, piece) <= 0 {
-            return false;
-        }
-
-        // 盤面内かチェック
-        if to_row < 0 || to_row >= 9 || to_col < 0 || to_col >= 9 {
-            return false;
-        }
-
-        // 空のマスかチェック
-        let to_pos = Position::new(to_row, to_col);
-        if let Some(_) = self.get_piece_at(to_pos) {
+        if !(0..9).contains(&to_row) || !(0..9).contains(&to_col) {
             return false;
         }
-
-        // 歩・香車の特殊ルール
-        match piece {
-            Piece::Pawn => {
-                // 二歩の禁止
-                if self.has_pawn_in_column_except(to_col, self.current_player, -1) {
-                    return false;
-                }
-            }
-            Piece::Lance => {
-                // 香車は最下段（先手）または最上段（後手）には打てない
-                let forbidden_row = if self.current_player == Player::Black { 8 } else { 0 };
-                if to_row == forbidden_row {
-                    return false;
-                }
-            }
-            Piece::Knight => {
-                // 桂馬は最下段・下から2段目（先手）または最上段・上から2段目（後手）には打てない
-                let forbidden_rows = if self.current_player == Player::Black { [7, 8] } else { [0, 1] };
-                if forbidden_rows.contains(&to_row) {
-                    return false;
-                }
-            }
-            _ => {}
-        }
-
-        true
+        let mv = Move::new_drop(piece, Position::new(to_row, to_col));
+        self.generate_legal_moves().contains(&mv)
     }
 
     #[wasm_bindgen]
@@ -476,23 +520,21 @@ impl Board {
         }
 
         // 盤面内かチェック
-        if to_row < 0 || to_row >= 9 || to_col < 0 || to_col >= 9 {
+        if !(0..9).contains(&to_row) || !(0..9).contains(&to_col) {
             reasons.push(format!("盤面外: ({}, {})", to_row, to_col));
         }
 
         // 空のマスかチェック
         let to_pos = Position::new(to_row, to_col);
-        if let Some(_) = self.get_piece_at(to_pos) {
+        if self.get_piece_at(to_pos).is_some() {
             reasons.push("空のマスでない".to_string());
         }
 
         // 歩・香車の特殊ルール
         match piece {
-            Piece::Pawn => {
-                // 二歩の禁止
-                if self.has_pawn_in_column_except(to_col, self.current_player, -1) {
-                    reasons.push("二歩の禁止".to_string());
-                }
+            // 二歩の禁止
+            Piece::Pawn if self.has_pawn_in_column_except(to_col, self.current_player, -1) => {
+                reasons.push("二歩の禁止".to_string());
             }
             Piece::Lance => {
                 // 香車は最下段（先手）または最上段（後手）には打てない
@@ -520,63 +562,7 @@ impl Board {
 
     #[wasm_bindgen]
     pub fn drop_piece(&mut self, piece: Piece, to_row: i32, to_col: i32) -> bool {
-        if !self.can_drop_piece(piece, to_row, to_col) {
-            return false;
-        }
-
-        if self.use_captured_piece(self.current_player, piece) {
-            self.pieces[to_row as usize][to_col as usize] = (piece, self.current_player);
-            self.current_player = if self.current_player == Player::Black {
-                Player::White
-            } else {
-                Player::Black
-            };
-            true
-        } else {
-            false
-        }
-    }
-
-    #[wasm_bindgen]
-    pub fn debug_board_state(&self) -> String {
-        let mut result = String::new();
-        result.push_str("盤面の状態:\n");
-        
-        for row in (0..9).rev() {
-            result.push_str(&format!("{}段目: ", row));
-            for col in 0..9 {
-                let pos = Position::new(row, col);
-                if let Some((piece, player)) = self.get_piece_at(pos) {
-                    let piece_name = match piece {
-                        Piece::Empty => "空",
-                        Piece::Pawn => "歩",
-                        Piece::Lance => "香",
-                        Piece::Knight => "桂",
-                        Piece::Silver => "銀",
-                        Piece::Gold => "金",
-                        Piece::Bishop => "角",
-                        Piece::Rook => "飛",
-                        Piece::King => if player == Player::Black { "玉" } else { "王" },
-                        Piece::PromotedPawn => "と",
-                        Piece::PromotedLance => "成香",
-                        Piece::PromotedKnight => "成桂",
-                        Piece::PromotedSilver => "成銀",
-                        Piece::PromotedBishop => "馬",
-                        Piece::PromotedRook => "龍",
-                    };
-                    let player_name = if player == Player::Black { "先手" } else { "後手" };
-                    result.push_str(&format!("{}{} ", piece_name, player_name));
-                } else {
-                    result.push_str("空 ");
-                }
-            }
-            result.push('\n');
-        }
-        
-        result.push_str(&format!("現在の手番: {}\n", 
-            if self.current_player == Player::Black { "先手" } else { "後手" }));
-        
-        result
+        self.drop_piece_with_outcome(piece, to_row, to_col).is_some()
     }
 
     // 二歩の判定の詳細を表示するデバッグメソッド
@@ -633,7 +619,7 @@ impl Board {
             result.push_str(&format!("{}の持ち駒:\n", player_name));
             
             let mut has_pieces = false;
-            let pieces = [Piece::Pawn, Piece::Lance, Piece::Knight, Piece::Silver, Piece::Gold, Piece::Bishop, Piece::Rook, Piece::King];
+            let pieces = [Piece::Pawn, Piece::Lance, Piece::Knight, Piece::Silver, Piece::Gold, Piece::Bishop, Piece::Rook];
             for piece in pieces {
                 let count = self.get_captured_piece_count(player, piece);
                 if count > 0 {
@@ -645,7 +631,6 @@ impl Board {
                         Piece::Gold => "金",
                         Piece::Bishop => "角",
                         Piece::Rook => "飛",
-                        Piece::King => "玉",
                         _ => "不明",
                     };
                     result.push_str(&format!("  {}: {}個\n", piece_name, count));
@@ -662,302 +647,119 @@ impl Board {
     }
 }
 
+impl Default for Board {
+    fn default() -> Self {
+        Board::new()
+    }
+}
+
 // 内部実装用のメソッドは#[wasm_bindgen]を付けない
 impl Board {
     fn initialize(&mut self) {
         // 初期配置を設定
-        // 先手（下側）の配置
-        self.pieces[0] = [
-            (Piece::Lance, Player::Black),
-            (Piece::Knight, Player::Black),
-            (Piece::Silver, Player::Black),
-            (Piece::Gold, Player::Black),
-            (Piece::King, Player::Black),
-            (Piece::Gold, Player::Black),
-            (Piece::Silver, Player::Black),
-            (Piece::Knight, Player::Black),
-            (Piece::Lance, Player::Black),
+        let back_rank = [
+            Piece::Lance,
+            Piece::Knight,
+            Piece::Silver,
+            Piece::Gold,
+            Piece::King,
+            Piece::Gold,
+            Piece::Silver,
+            Piece::Knight,
+            Piece::Lance,
         ];
-        self.pieces[1][1] = (Piece::Bishop, Player::Black);
-        self.pieces[1][7] = (Piece::Rook, Player::Black);
-        for i in 0..9 {
-            self.pieces[2][i] = (Piece::Pawn, Player::Black);
+
+        // 先手（下側）の配置
+        for (col, &piece) in back_rank.iter().enumerate() {
+            self.put_piece(square(0, col), piece, Player::Black);
+        }
+        self.put_piece(square(1, 1), Piece::Bishop, Player::Black);
+        self.put_piece(square(1, 7), Piece::Rook, Player::Black);
+        for col in 0..9 {
+            self.put_piece(square(2, col), Piece::Pawn, Player::Black);
         }
 
         // 後手（上側）の配置
-        self.pieces[8] = [
-            (Piece::Lance, Player::White),
-            (Piece::Knight, Player::White),
-            (Piece::Silver, Player::White),
-            (Piece::Gold, Player::White),
-            (Piece::King, Player::White),
-            (Piece::Gold, Player::White),
-            (Piece::Silver, Player::White),
-            (Piece::Knight, Player::White),
-            (Piece::Lance, Player::White),
-        ];
-        self.pieces[7][1] = (Piece::Rook, Player::White);
-        self.pieces[7][7] = (Piece::Bishop, Player::White);
-        for i in 0..9 {
-            self.pieces[6][i] = (Piece::Pawn, Player::White);
+        for (col, &piece) in back_rank.iter().enumerate() {
+            self.put_piece(square(8, col), piece, Player::White);
+        }
+        self.put_piece(square(7, 1), Piece::Rook, Player::White);
+        self.put_piece(square(7, 7), Piece::Bishop, Player::White);
+        for col in 0..9 {
+            self.put_piece(square(6, col), Piece::Pawn, Player::White);
         }
     }
 
-    fn get_piece_at(&self, pos: Position) -> Option<(Piece, Player)> {
-        if !pos.is_valid() {
-            return None;
+    // マスに駒を置く（既に駒がある場合は置き換える）。盤面の変更は必ずここかremove_pieceを通す
+    fn put_piece(&mut self, sq: usize, piece: Piece, player: Player) {
+        self.remove_piece(sq);
+        if piece == Piece::Empty {
+            return;
         }
-        let (piece, player) = self.pieces[pos.row as usize][pos.column as usize];
+        self.pieces[sq] = (piece, player);
+        self.by_player[player as usize].set(sq);
+        self.by_piece[piece as usize].set(sq);
+        self.hash ^= zobrist::PIECE_KEYS[player as usize][piece as usize][sq];
+    }
+
+    fn remove_piece(&mut self, sq: usize) {
+        let (piece, player) = self.pieces[sq];
         if piece == Piece::Empty {
-            None
-        } else {
-            Some((piece, player))
+            return;
         }
+        self.by_player[player as usize].clear(sq);
+        self.by_piece[piece as usize].clear(sq);
+        self.pieces[sq] = (Piece::Empty, Player::Black);
+        self.hash ^= zobrist::PIECE_KEYS[player as usize][piece as usize][sq];
     }
 
-    fn is_valid_position(&self, pos: Position) -> bool {
-        pos.is_valid()
+    // 持ち駒の数を変更する。持ち駒の変更は必ずここを通す
+    fn set_hand_count(&mut self, player_index: usize, piece_index: usize, count: i32) {
+        let keys = &zobrist::HAND_KEYS[player_index][piece_index];
+        let old = self.captured_pieces[player_index][piece_index];
+        self.hash ^= keys[(old as usize).min(zobrist::MAX_HAND_COUNT)];
+        self.hash ^= keys[(count as usize).min(zobrist::MAX_HAND_COUNT)];
+        self.captured_pieces[player_index][piece_index] = count;
     }
 
-    fn is_empty_or_opponent(&self, pos: Position, player: Player) -> bool {
-        if let Some((_, piece_player)) = self.get_piece_at(pos) {
-            piece_player != player
-        } else {
-            true
-        }
+    fn switch_player(&mut self) {
+        self.current_player = self.current_player.opponent();
+        self.hash ^= zobrist::SIDE_KEY;
     }
 
-    fn get_piece_moves(&self, from: Position, piece: Piece, player: Player) -> Vec<Position> {
-        let mut moves = Vec::new();
-        
-        match piece {
-            Piece::Pawn => {
-                // 歩: 前方1マスのみ
-                let dir = if player == Player::Black { 1 } else { -1 };
-                let new_row = from.row + dir;
-                let new_col = from.column;
-                
-                if new_row >= 0 && new_row < 9 && new_col >= 0 && new_col < 9 {
-                    let new_pos = Position::new(new_row, new_col);
-                    if self.is_empty_or_opponent(new_pos, player) {
-                        // 二歩の禁止チェック
-                        if !self.has_pawn_in_column_except(new_col, player, from.row) {
-                            moves.push(new_pos);
-                        }
-                    }
-                }
-            }
-            
-            Piece::Lance => {
-                // 香車: 前方一直線
-                let dir = if player == Player::Black { 1 } else { -1 };
-                let mut current_row = from.row + dir;
-                
-                while current_row >= 0 && current_row < 9 {
-                    let new_pos = Position::new(current_row, from.column);
-                    if let Some((_, piece_player)) = self.get_piece_at(new_pos) {
-                        if piece_player != player {
-                            moves.push(new_pos);
-                        }
-                        break; // 他の駒にぶつかったら停止
-                    }
-                    moves.push(new_pos);
-                    current_row += dir;
-                }
-            }
-            
-            Piece::Knight => {
-                // 桂馬: 特殊な動き（前方2マス+左右1マス）
-                let dir = if player == Player::Black { 2 } else { -2 };
-                let new_row = from.row + dir;
-                
-                if new_row >= 0 && new_row < 9 {
-                    // 左桂馬
-                    let left_col = from.column - 1;
-                    if left_col >= 0 {
-                        let new_pos = Position::new(new_row, left_col);
-                        if self.is_empty_or_opponent(new_pos, player) {
-                            moves.push(new_pos);
-                        }
-                    }
-                    
-                    // 右桂馬
-                    let right_col = from.column + 1;
-                    if right_col < 9 {
-                        let new_pos = Position::new(new_row, right_col);
-                        if self.is_empty_or_opponent(new_pos, player) {
-                            moves.push(new_pos);
-                        }
-                    }
-                }
-            }
-            
-            Piece::Silver => {
-                // 銀: 前方3方向+後方2方向
-                let dir = if player == Player::Black { 1 } else { -1 };
-                let directions = vec![
-                    (dir, -1),   // 前方左斜め
-                    (dir, 0),    // 前方
-                    (dir, 1),    // 前方右斜め
-                    (-dir, -1),  // 後方左斜め
-                    (-dir, 1),   // 後方右斜め
-                ];
-                
-                for (dr, dc) in directions {
-                    let new_row = from.row + dr;
-                    let new_col = from.column + dc;
-                    
-                    if new_row >= 0 && new_row < 9 && new_col >= 0 && new_col < 9 {
-                        let new_pos = Position::new(new_row, new_col);
-                        if self.is_empty_or_opponent(new_pos, player) {
-                            moves.push(new_pos);
-                        }
-                    }
-                }
-            }
-            
-            Piece::Gold | Piece::PromotedPawn | Piece::PromotedLance | 
-            Piece::PromotedKnight | Piece::PromotedSilver => {
-                // 金・成り駒: 前方3方向+横2方向+後方1方向
-                let dir = if player == Player::Black { 1 } else { -1 };
-                let directions = vec![
-                    (dir, -1),   // 前方左斜め
-                    (dir, 0),    // 前方
-                    (dir, 1),    // 前方右斜め
-                    (0, -1),     // 左
-                    (0, 1),      // 右
-                    (-dir, 0),   // 後方
-                ];
-                
-                for (dr, dc) in directions {
-                    let new_row = from.row + dr;
-                    let new_col = from.column + dc;
-                    
-                    if new_row >= 0 && new_row < 9 && new_col >= 0 && new_col < 9 {
-                        let new_pos = Position::new(new_row, new_col);
-                        if self.is_empty_or_opponent(new_pos, player) {
-                            moves.push(new_pos);
-                        }
-                    }
-                }
-            }
-            
-            Piece::Bishop => {
-                // 角行: 斜め4方向の一直線
-                let directions = vec![(-1, -1), (-1, 1), (1, -1), (1, 1)];
-                self.add_sliding_moves(from, directions, player, &mut moves);
-            }
-            
-            Piece::PromotedBishop => {
-                // 馬: 角行の動き+縦横1マス
-                let directions = vec![(-1, -1), (-1, 1), (1, -1), (1, 1)];
-                self.add_sliding_moves(from, directions, player, &mut moves);
-                
-                // 縦横1マス
-                let orthogonal_directions = vec![(-1, 0), (1, 0), (0, -1), (0, 1)];
-                for (dr, dc) in orthogonal_directions {
-                    let new_row = from.row + dr;
-                    let new_col = from.column + dc;
-                    
-                    if new_row >= 0 && new_row < 9 && new_col >= 0 && new_col < 9 {
-                        let new_pos = Position::new(new_row, new_col);
-                        if self.is_empty_or_opponent(new_pos, player) {
-                            moves.push(new_pos);
-                        }
-                    }
-                }
-            }
-            
-            Piece::Rook => {
-                // 飛車: 縦横4方向の一直線
-                let directions = vec![(-1, 0), (1, 0), (0, -1), (0, 1)];
-                self.add_sliding_moves(from, directions, player, &mut moves);
-            }
-            
-            Piece::PromotedRook => {
-                // 龍: 飛車の動き+斜め1マス
-                let directions = vec![(-1, 0), (1, 0), (0, -1), (0, 1)];
-                self.add_sliding_moves(from, directions, player, &mut moves);
-                
-                // 斜め1マス
-                let diagonal_directions = vec![(-1, -1), (-1, 1), (1, -1), (1, 1)];
-                for (dr, dc) in diagonal_directions {
-                    let new_row = from.row + dr;
-                    let new_col = from.column + dc;
-                    
-                    if new_row >= 0 && new_row < 9 && new_col >= 0 && new_col < 9 {
-                        let new_pos = Position::new(new_row, new_col);
-                        if self.is_empty_or_opponent(new_pos, player) {
-                            moves.push(new_pos);
-                        }
-                    }
-                }
-            }
-            
-            Piece::King => {
-                // 玉: 全方向1マス
-                let directions = vec![
-                    (-1, -1), (-1, 0), (-1, 1),
-                    (0, -1), (0, 1),
-                    (1, -1), (1, 0), (1, 1),
-                ];
-                
-                for (dr, dc) in directions {
-                    let new_row = from.row + dr;
-                    let new_col = from.column + dc;
-                    
-                    if new_row >= 0 && new_row < 9 && new_col >= 0 && new_col < 9 {
-                        let new_pos = Position::new(new_row, new_col);
-                        if self.is_empty_or_opponent(new_pos, player) {
-                            moves.push(new_pos);
-                        }
-                    }
-                }
-            }
-            
-            Piece::Empty => {}
-        }
+    // 駒があるマス全体
+    fn occupied(&self) -> Bitboard {
+        self.by_player[0] | self.by_player[1]
+    }
 
-        moves
+    // 指定した手番・種類の駒があるマス
+    fn pieces_of(&self, piece: Piece, player: Player) -> Bitboard {
+        self.by_piece[piece as usize] & self.by_player[player as usize]
     }
 
-    // 一直線に動ける駒（香車、飛車、角行）の移動可能位置を計算
-    fn add_sliding_moves(&self, from: Position, directions: Vec<(i32, i32)>, player: Player, moves: &mut Vec<Position>) {
-        for (dr, dc) in directions {
-            let mut current_row = from.row + dr;
-            let mut current_col = from.column + dc;
-            
-            while current_row >= 0 && current_row < 9 && current_col >= 0 && current_col < 9 {
-                let new_pos = Position::new(current_row, current_col);
-                if let Some((_, piece_player)) = self.get_piece_at(new_pos) {
-                    if piece_player != player {
-                        moves.push(new_pos);
-                    }
-                    break; // 他の駒にぶつかったら停止
-                }
-                moves.push(new_pos);
-                current_row += dr;
-                current_col += dc;
-            }
+    fn get_piece_at(&self, pos: Position) -> Option<(Piece, Player)> {
+        if !pos.is_valid() {
+            return None;
+        }
+        let (piece, player) = self.pieces[square_of(pos)];
+        if piece == Piece::Empty {
+            None
+        } else {
+            Some((piece, player))
         }
     }
 
     // 二歩の禁止チェック（同じ列のみ判定、移動元の歩を除外）
     fn has_pawn_in_column_except(&self, col: i32, player: Player, except_row: i32) -> bool {
-        // 指定された列（col）のみをチェック（同じ段は判定しない）
-        for row in 0..9 {
-            if row == except_row {
-                continue; // 移動元の位置は除外
-            }
-            let pos = Position::new(row, col);
-            if let Some((piece, piece_player)) = self.get_piece_at(pos) {
-                // 同じ列に自分の歩があるかチェック（相手の歩は判定に入れない）
-                if piece == Piece::Pawn && piece_player == player {
-                    return true;
-                }
-            }
+        if !(0..9).contains(&col) {
+            return false;
+        }
+        // 指定された列（col）のみをチェック（相手の歩は判定に入れない）
+        let mut pawns = self.pieces_of(Piece::Pawn, player) & Bitboard::file(col as usize);
+        if (0..9).contains(&except_row) {
+            pawns.clear(square(except_row as usize, col as usize)); // 移動元の位置は除外
         }
-        false
+        !pawns.is_empty()
     }
 
     fn get_promoted_piece(&self, piece: Piece) -> Option<Piece> {
@@ -994,8 +796,7 @@ impl Board {
             Piece::Gold => 4,
             Piece::Bishop => 5,
             Piece::Rook => 6,
-            Piece::King => 7,
-            _ => -1, // 成り駒や空の駒は持ち駒にならない
+            _ => -1, // 玉・成り駒・空の駒は持ち駒にならない
         }
     }
 
@@ -0,0 +1,89 @@
+use wasm_bindgen::prelude::*;
+
+use crate::bitboard::square_of;
+use crate::{Board, Move, Piece};
+
+// 指し手を戻すために必要な情報
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq)]
+pub struct UndoInfo {
+    captured_piece: Piece, // 取った駒（成り駒は成ったまま。取っていない場合はEmpty）
+    hash: u64,             // 指す前の局面のハッシュ値
+}
+
+#[wasm_bindgen]
+impl UndoInfo {
+    #[wasm_bindgen]
+    pub fn get_captured_piece(&self) -> Piece {
+        self.captured_piece
+    }
+}
+
+#[wasm_bindgen]
+impl Board {
+    // 指し手を適用し、戻すための情報を返す（合法性は確認しないので、生成した合法手を渡すこと）
+    #[wasm_bindgen]
+    pub fn do_move(&mut self, mv: Move) -> UndoInfo {
+        let player = self.current_player;
+        let hash = self.hash;
+        let to = square_of(mv.to);
+        let mut captured_piece = Piece::Empty;
+
+        if mv.is_drop() {
+            self.use_captured_piece(player, mv.piece);
+            self.put_piece(to, mv.piece, player);
+        } else {
+            let from = square_of(mv.from);
+            let (piece, _) = self.pieces[from];
+            let (target_piece, target_player) = self.pieces[to];
+            if target_piece != Piece::Empty && target_player != player {
+                // 成り駒は元の駒に戻して持ち駒に追加
+                captured_piece = target_piece;
+                if target_piece == Piece::King {
+                    // 玉は持ち駒にならない。取られた時点で対局終了
+                    self.captured_king = Some(target_player);
+                } else {
+                    let original_piece = self.get_original_piece(target_piece);
+                    self.add_captured_piece(player, original_piece);
+                }
+            }
+            let final_piece = if mv.promote {
+                self.get_promoted_piece(piece).unwrap_or(piece)
+            } else {
+                piece
+            };
+            self.remove_piece(from);
+            self.put_piece(to, final_piece, player);
+        }
+        self.switch_player();
+
+        UndoInfo { captured_piece, hash }
+    }
+
+    // do_move で指した手を戻す
+    #[wasm_bindgen]
+    pub fn undo_move(&mut self, mv: Move, undo: UndoInfo) {
+        self.switch_player();
+        let player = self.current_player;
+        let to = square_of(mv.to);
+
+        if mv.is_drop() {
+            self.remove_piece(to);
+            self.add_captured_piece(player, mv.piece);
+        } else {
+            let from = square_of(mv.from);
+            self.remove_piece(to);
+            self.put_piece(from, mv.piece, player);
+            if undo.captured_piece == Piece::King {
+                self.put_piece(to, undo.captured_piece, player.opponent());
+                self.captured_king = None;
+            } else if undo.captured_piece != Piece::Empty {
+                self.put_piece(to, undo.captured_piece, player.opponent());
+                let original_piece = self.get_original_piece(undo.captured_piece);
+                self.use_captured_piece(player, original_piece);
+            }
+        }
+
+        self.hash = undo.hash;
+    }
+}
@@ -0,0 +1,370 @@
+use wasm_bindgen::prelude::*;
+
+use crate::bitboard::{position_of, square_of, tables, Bitboard};
+use crate::{Board, Move, Piece, Player};
+
+// 持ち駒として打てる駒の種類
+pub(crate) const HAND_PIECES: [Piece; 7] = [
+    Piece::Pawn,
+    Piece::Lance,
+    Piece::Knight,
+    Piece::Silver,
+    Piece::Gold,
+    Piece::Bishop,
+    Piece::Rook,
+];
+
+// Piece の値から駒を引くための表
+pub(crate) const ALL_PIECES: [Piece; 15] = [
+    Piece::Empty,
+    Piece::Pawn,
+    Piece::Lance,
+    Piece::Knight,
+    Piece::Silver,
+    Piece::Gold,
+    Piece::Bishop,
+    Piece::Rook,
+    Piece::King,
+    Piece::PromotedPawn,
+    Piece::PromotedLance,
+    Piece::PromotedKnight,
+    Piece::PromotedSilver,
+    Piece::PromotedBishop,
+    Piece::PromotedRook,
+];
+
+#[wasm_bindgen]
+impl Board {
+    // 指定したプレイヤーの玉に王手がかかっているか
+    #[wasm_bindgen]
+    pub fn is_in_check(&self, player: Player) -> bool {
+        match self.king_square(player) {
+            Some(king) => !self.attackers_to_square(king, player.opponent(), self.occupied()).is_empty(),
+            None => false,
+        }
+    }
+
+    // 手番側の合法手をすべて生成する（成る・成らないの選択と持ち駒を打つ手を含む）
+    #[wasm_bindgen]
+    pub fn generate_legal_moves(&self) -> Vec<Move> {
+        let mut moves = Vec::with_capacity(128);
+        self.generate_moves(&mut moves);
+        moves
+    }
+
+    // 手番側の王手になる合法手をすべて生成する（開き王手・持ち駒を打つ王手を含む）
+    #[wasm_bindgen]
+    pub fn generate_checks(&self) -> Vec<Move> {
+        let t = tables();
+        let us = self.current_player;
+        let them = us.opponent();
+        let king = match self.king_square(them) {
+            Some(king) => king,
+            None => return Vec::new(),
+        };
+        let occupied = self.occupied();
+        let own = self.by_player[us as usize];
+
+        // 駒の種類ごとに、そこに置けば相手玉に利くマス（玉の位置から相手側の同じ駒の利きを逆算する）
+        let mut check_squares = [Bitboard::EMPTY; 15];
+        for (index, squares) in check_squares.iter_mut().enumerate().skip(1) {
+            *squares = t.attacks(ALL_PIECES[index], them, king, occupied);
+        }
+        // 動くと開き王手になる駒（自分の飛び駒と相手玉の間にある唯一の自駒）
+        let discovered = self.line_blockers(king, them) & own;
+        let gives_check = |from: usize, to: usize, moved: Piece| {
+            check_squares[moved as usize].has(to) || (discovered.has(from) && !t.line(king, from).has(to))
+        };
+
+        let mut moves = Vec::new();
+
+        // 王手されている場合は応手の中から王手になるものを選ぶ
+        if self.is_in_check(us) {
+            self.generate_moves(&mut moves);
+            moves.retain(|mv| {
+                let moved = if mv.promote { self.get_promoted_piece(mv.piece).unwrap_or(mv.piece) } else { mv.piece };
+                if mv.is_drop() {
+                    check_squares[moved as usize].has(square_of(mv.to))
+                } else {
+                    gives_check(square_of(mv.from), square_of(mv.to), moved)
+                }
+            });
+            return moves;
+        }
+
+        let own_king = self.king_square(us);
+        let pinned = own_king.map_or(Bitboard::EMPTY, |k| self.line_blockers(k, us) & own);
+
+        for from in own {
+            let (piece, _) = self.pieces[from];
+            let mut targets = t.attacks(piece, us, from, occupied) & !own;
+            if !discovered.has(from) {
+                let mut reachable = check_squares[piece as usize];
+                if self.can_promote_piece(piece) {
+                    reachable |= check_squares[self.get_promoted_piece(piece).unwrap_or(piece) as usize];
+                }
+                targets &= reachable;
+            }
+            if Some(from) == own_king {
+                // 玉が動いて開き王手になる手（移動先に相手の利きがないこと）
+                let without_king = occupied ^ Bitboard::from_square(from);
+                for to in targets {
+                    if self.attackers_to_square(to, them, without_king).is_empty() && gives_check(from, to, piece) {
+                        self.push_move_choices(from, to, piece, us, &mut moves);
+                    }
+                }
+                continue;
+            }
+            if let Some(k) = own_king {
+                if pinned.has(from) {
+                    targets &= t.line(k, from);
+                }
+            }
+            for to in targets {
+                let mut choices = Vec::with_capacity(2);
+                self.push_move_choices(from, to, piece, us, &mut choices);
+                for mv in choices {
+                    let moved = if mv.promote { self.get_promoted_piece(piece).unwrap_or(piece) } else { piece };
+                    if gives_check(from, to, moved) {
+                        moves.push(mv);
+                    }
+                }
+            }
+        }
+
+        // 持ち駒を打つ王手
+        for piece in HAND_PIECES {
+            let targets = check_squares[piece as usize] & !occupied;
+            self.push_drops(piece, us, targets, &mut moves);
+        }
+
+        moves
+    }
+
+    // 手番側が王手されているときの応手（玉の移動・王手駒の取り・合駒）をすべて生成する
+    #[wasm_bindgen]
+    pub fn generate_evasions(&self) -> Vec<Move> {
+        let us = self.current_player;
+        let king = match self.king_square(us) {
+            Some(king) => king,
+            None => return Vec::new(),
+        };
+        let checkers = self.attackers_to_square(king, us.opponent(), self.occupied());
+        let mut moves = Vec::new();
+        if !checkers.is_empty() {
+            self.push_evasions(king, checkers, &mut moves);
+        }
+        moves
+    }
+}
+
+impl Board {
+    pub(crate) fn king_square(&self, player: Player) -> Option<usize> {
+        let kings = self.pieces_of(Piece::King, player);
+        if kings.is_empty() {
+            None
+        } else {
+            Some(kings.lsb())
+        }
+    }
+
+    // 指定したマスに利いている駒（occupied は飛び駒の遮りに使う駒の配置）
+    pub(crate) fn attackers_to_square(&self, sq: usize, by: Player, occupied: Bitboard) -> Bitboard {
+        let t = tables();
+        // 利きは先後で対称なので、相手側の同じ駒の動きを逆にたどる
+        let opponent = by.opponent();
+        let gold_like = self.by_piece[Piece::Gold as usize]
+            | self.by_piece[Piece::PromotedPawn as usize]
+            | self.by_piece[Piece::PromotedLance as usize]
+            | self.by_piece[Piece::PromotedKnight as usize]
+            | self.by_piece[Piece::PromotedSilver as usize];
+        let king_like = self.by_piece[Piece::King as usize]
+            | self.by_piece[Piece::PromotedBishop as usize]
+            | self.by_piece[Piece::PromotedRook as usize];
+        let bishop_like = self.by_piece[Piece::Bishop as usize] | self.by_piece[Piece::PromotedBishop as usize];
+        let rook_like = self.by_piece[Piece::Rook as usize] | self.by_piece[Piece::PromotedRook as usize];
+
+        let attackers = (t.step_attacks(Piece::Pawn, opponent, sq) & self.by_piece[Piece::Pawn as usize])
+            | (t.step_attacks(Piece::Knight, opponent, sq) & self.by_piece[Piece::Knight as usize])
+            | (t.step_attacks(Piece::Silver, opponent, sq) & self.by_piece[Piece::Silver as usize])
+            | (t.step_attacks(Piece::Gold, opponent, sq) & gold_like)
+            | (t.step_attacks(Piece::King, opponent, sq) & king_like)
+            | (t.lance_attacks(opponent, sq, occupied) & self.by_piece[Piece::Lance as usize])
+            | (t.bishop_attacks(sq, occupied) & bishop_like)
+            | (t.rook_attacks(sq, occupied) & rook_like);
+        attackers & self.by_player[by as usize]
+    }
+
+    // 玉と相手の飛び駒の間にある唯一の駒（先後どちらの駒も含む）
+    pub(crate) fn line_blockers(&self, king: usize, king_owner: Player) -> Bitboard {
+        let t = tables();
+        let attacker = king_owner.opponent();
+        let occupied = self.occupied();
+        let rook_like = self.pieces_of(Piece::Rook, attacker) | self.pieces_of(Piece::PromotedRook, attacker);
+        let bishop_like = self.pieces_of(Piece::Bishop, attacker) | self.pieces_of(Piece::PromotedBishop, attacker);
+        let snipers = (t.rook_attacks(king, Bitboard::EMPTY) & rook_like)
+            | (t.bishop_attacks(king, Bitboard::EMPTY) & bishop_like)
+            | (t.lance_attacks(king_owner, king, Bitboard::EMPTY) & self.pieces_of(Piece::Lance, attacker));
+
+        let mut blockers = Bitboard::EMPTY;
+        for sniper in snipers {
+            let between = t.between(king, sniper) & occupied;
+            if between.count() == 1 {
+                blockers |= between;
+            }
+        }
+        blockers
+    }
+
+    // 手番側の合法手を生成する。王手されている場合は応手だけを生成する
+    pub(crate) fn generate_moves(&self, moves: &mut Vec<Move>) {
+        let us = self.current_player;
+        let king = self.king_square(us);
+        if let Some(k) = king {
+            let checkers = self.attackers_to_square(k, us.opponent(), self.occupied());
+            if !checkers.is_empty() {
+                self.push_evasions(k, checkers, moves);
+                return;
+            }
+            self.push_king_moves(k, moves);
+        }
+        self.push_piece_moves(king, !self.by_player[us as usize], moves);
+        for piece in HAND_PIECES {
+            self.push_drops(piece, us, !self.occupied(), moves);
+        }
+    }
+
+    // 王手されているときの応手（玉の移動・王手駒の取り・合駒）を追加する
+    fn push_evasions(&self, king: usize, checkers: Bitboard, moves: &mut Vec<Move>) {
+        self.push_king_moves(king, moves);
+
+        // 両王手の場合は玉を動かすしかない
+        if checkers.count() >= 2 {
+            return;
+        }
+
+        // 王手をかけている駒を取るか、間に駒を移動する・打つ
+        let between = tables().between(checkers.lsb(), king);
+        self.push_piece_moves(Some(king), between | checkers, moves);
+        for piece in HAND_PIECES {
+            self.push_drops(piece, self.current_player, between, moves);
+        }
+    }
+
+    // 玉の移動（玉を取り除いた配置で利きを調べ、飛び駒の延長線上に逃げないようにする）
+    fn push_king_moves(&self, king: usize, moves: &mut Vec<Move>) {
+        let us = self.current_player;
+        let without_king = self.occupied() ^ Bitboard::from_square(king);
+        for to in tables().step_attacks(Piece::King, us, king) & !self.by_player[us as usize] {
+            if self.attackers_to_square(to, us.opponent(), without_king).is_empty() {
+                moves.push(Move::new(position_of(king), position_of(to), Piece::King, false));
+            }
+        }
+    }
+
+    // 玉以外の盤上の駒を targets のマスへ動かす手を追加する
+    fn push_piece_moves(&self, king: Option<usize>, targets: Bitboard, moves: &mut Vec<Move>) {
+        let t = tables();
+        let us = self.current_player;
+        let occupied = self.occupied();
+        let own = self.by_player[us as usize];
+        let pinned = king.map_or(Bitboard::EMPTY, |k| self.line_blockers(k, us) & own);
+        let others = match king {
+            Some(k) => own ^ Bitboard::from_square(k),
+            None => own,
+        };
+        for from in others {
+            let (piece, _) = self.pieces[from];
+            let mut reachable = t.attacks(piece, us, from, occupied) & targets;
+            if let Some(k) = king {
+                if pinned.has(from) {
+                    // ピンされた駒は玉との直線上でしか動けない
+                    reachable &= t.line(k, from);
+                }
+            }
+            for to in reachable {
+                self.push_move_choices(from, to, piece, us, moves);
+            }
+        }
+    }
+
+    // 移動先に対して、成る・成らないの選択肢を指し手として追加する
+    fn push_move_choices(&self, from: usize, to: usize, piece: Piece, player: Player, moves: &mut Vec<Move>) {
+        let zone = Bitboard::promotion_zone(player);
+        let from_pos = position_of(from);
+        let to_pos = position_of(to);
+        if self.can_promote_piece(piece) && (zone.has(from) || zone.has(to)) {
+            moves.push(Move::new(from_pos, to_pos, piece, true));
+        }
+        // 行き所のない駒になる場合は成らない手を生成しない
+        if !is_dead_square(piece, player, to_pos.row) {
+            moves.push(Move::new(from_pos, to_pos, piece, false));
+        }
+    }
+
+    // 持ち駒を打つ手を追加する（二歩・行き所のない駒・打ち歩詰めを除く）
+    fn push_drops(&self, piece: Piece, player: Player, targets: Bitboard, moves: &mut Vec<Move>) {
+        if self.get_captured_piece_count(player, piece) <= 0 {
+            return;
+        }
+        let mut targets = targets;
+        match piece {
+            Piece::Pawn => {
+                targets &= !last_ranks(player, 1);
+                for pawn in self.pieces_of(Piece::Pawn, player) {
+                    targets &= !Bitboard::file(pawn % 9);
+                }
+            }
+            Piece::Lance => targets &= !last_ranks(player, 1),
+            Piece::Knight => targets &= !last_ranks(player, 2),
+            _ => {}
+        }
+        for to in targets {
+            let mv = Move::new_drop(piece, position_of(to));
+            if piece == Piece::Pawn && self.is_pawn_drop_mate(mv) {
+                continue;
+            }
+            moves.push(mv);
+        }
+    }
+
+    // 歩を打って相手玉が詰むか（打ち歩詰めの判定）
+    fn is_pawn_drop_mate(&self, mv: Move) -> bool {
+        let them = self.current_player.opponent();
+        let king = match self.king_square(them) {
+            Some(king) => king,
+            None => return false,
+        };
+        // 玉の正面に打つ場合のみ王手になる
+        if !tables().step_attacks(Piece::Pawn, self.current_player, square_of(mv.to)).has(king) {
+            return false;
+        }
+        let mut next = self.clone();
+        next.do_move(mv);
+        let mut replies = Vec::new();
+        next.generate_moves(&mut replies);
+        replies.is_empty()
+    }
+}
+
+// 指定したプレイヤーから見て奥から count 段
+fn last_ranks(player: Player, count: usize) -> Bitboard {
+    let mut bb = Bitboard::EMPTY;
+    for i in 0..count {
+        bb |= match player {
+            Player::Black => Bitboard::rank(8 - i),
+            Player::White => Bitboard::rank(i),
+        };
+    }
+    bb
+}
+
+// 行き所のない駒になるマスか（歩・香の最終段、桂の最終2段）
+pub(crate) fn is_dead_square(piece: Piece, player: Player, row: i32) -> bool {
+    let rank_from_end = if player == Player::Black { 8 - row } else { row };
+    match piece {
+        Piece::Pawn | Piece::Lance => rank_from_end < 1,
+        Piece::Knight => rank_from_end < 2,
+        _ => false,
+    }
+}
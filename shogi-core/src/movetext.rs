@@ -0,0 +1,232 @@
+// 日本語で入力した指し手の読み取り（キーボードで棋譜を入力するため）
+//
+// "76歩"、"７六歩"、"▲2二角成"、"同銀"、"5五角打"、"58金右"、"７六歩(77)" のような表記を受け付け、
+// 現在の局面の合法手のうち当てはまるものを探す。KIFと同じく、右・左・上・引・寄・直で動かす駒を区別する
+
+use wasm_bindgen::prelude::*;
+
+use crate::notation::{kif_move, parse_file_char, parse_rank_char};
+use crate::{Board, Move, Piece, Player, Position};
+
+// 指し手の読み取り結果
+#[wasm_bindgen]
+pub struct MoveTextResult {
+    candidates: Vec<Move>,
+    error: Option<String>,
+}
+
+#[wasm_bindgen]
+impl MoveTextResult {
+    // 当てはまる合法手がちょうど1つか
+    #[wasm_bindgen]
+    pub fn is_unique(&self) -> bool {
+        self.candidates.len() == 1
+    }
+
+    #[wasm_bindgen]
+    pub fn get_move(&self) -> Option<Move> {
+        if self.is_unique() {
+            self.candidates.first().copied()
+        } else {
+            None
+        }
+    }
+
+    // 当てはまる合法手（複数ある場合は曖昧）
+    #[wasm_bindgen]
+    pub fn get_candidates(&self) -> Vec<Move> {
+        self.candidates.clone()
+    }
+
+    // 候補の表記（例: "２二角(88)"、"２二角成(88)"）
+    #[wasm_bindgen]
+    pub fn get_candidate_names(&self) -> Vec<String> {
+        self.candidates.iter().map(|&mv| kif_move(mv, false)).collect()
+    }
+
+    // 読み取れなかった理由（候補があれば None）
+    #[wasm_bindgen]
+    pub fn get_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+}
+
+impl MoveTextResult {
+    fn error(message: String) -> MoveTextResult {
+        MoveTextResult { candidates: Vec::new(), error: Some(message) }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Promotion {
+    Unspecified,
+    Promote,
+    NoPromote,
+}
+
+// 入力を分解したもの
+struct MoveText {
+    player: Option<Player>,
+    to: Option<Position>, // None は「同」
+    piece: Piece,
+    modifiers: Vec<char>, // 右・左・上・引・寄・直
+    promotion: Promotion,
+    drop: bool,
+    from: Option<Position>, // "(77)" で移動元を指定した場合
+}
+
+// 駒の名前（長い名前から順に調べる）
+const PIECE_NAMES: [(&str, Piece); 20] = [
+    ("成香", Piece::PromotedLance),
+    ("成桂", Piece::PromotedKnight),
+    ("成銀", Piece::PromotedSilver),
+    ("と金", Piece::PromotedPawn),
+    ("歩", Piece::Pawn),
+    ("香", Piece::Lance),
+    ("桂", Piece::Knight),
+    ("銀", Piece::Silver),
+    ("金", Piece::Gold),
+    ("角", Piece::Bishop),
+    ("飛", Piece::Rook),
+    ("玉", Piece::King),
+    ("王", Piece::King),
+    ("と", Piece::PromotedPawn),
+    ("杏", Piece::PromotedLance),
+    ("圭", Piece::PromotedKnight),
+    ("全", Piece::PromotedSilver),
+    ("馬", Piece::PromotedBishop),
+    ("龍", Piece::PromotedRook),
+    ("竜", Piece::PromotedRook),
+];
+
+fn parse_square(file: char, rank: char) -> Option<Position> {
+    let file = parse_file_char(file)?;
+    let rank = parse_rank_char(rank)?;
+    Some(Position::new(9 - rank as i32, 9 - file as i32))
+}
+
+fn parse_move_text(text: &str) -> Option<MoveText> {
+    let text: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut rest = text.as_str();
+
+    let player = match rest.chars().next()? {
+        '▲' | '☗' => Some(Player::Black),
+        '△' | '☖' | '▽' => Some(Player::White),
+        _ => None,
+    };
+    if player.is_some() {
+        rest = &rest[rest.chars().next()?.len_utf8()..];
+    }
+
+    let to = if let Some(after) = rest.strip_prefix('同').or_else(|| rest.strip_prefix('仝')) {
+        rest = after;
+        None
+    } else {
+        let mut chars = rest.chars();
+        let (file, rank) = (chars.next()?, chars.next()?);
+        rest = chars.as_str();
+        Some(parse_square(file, rank)?)
+    };
+
+    let (name, piece) = PIECE_NAMES
+        .iter()
+        .find(|(name, _)| rest.starts_with(name))
+        .copied()?;
+    rest = &rest[name.len()..];
+
+    // 末尾の "(77)"（KIFの移動元）
+    let mut from = None;
+    if let Some(open) = rest.find(['(', '（']) {
+        let inner: Vec<char> = rest[open..].chars().skip(1).take_while(|&c| c != ')' && c != '）').collect();
+        if inner.len() != 2 {
+            return None;
+        }
+        from = Some(parse_square(inner[0], inner[1])?);
+        rest = &rest[..open];
+    }
+
+    let mut promotion = Promotion::Unspecified;
+    let mut drop = false;
+    if let Some(before) = rest.strip_suffix("不成").or_else(|| rest.strip_suffix('生')) {
+        promotion = Promotion::NoPromote;
+        rest = before;
+    } else if let Some(before) = rest.strip_suffix('成') {
+        promotion = Promotion::Promote;
+        rest = before;
+    } else if let Some(before) = rest.strip_suffix('打') {
+        drop = true;
+        rest = before;
+    }
+
+    let modifiers: Vec<char> = rest.chars().collect();
+    if !modifiers.iter().all(|c| "右左直上引寄".contains(*c)) {
+        return None;
+    }
+
+    Some(MoveText { player, to, piece, modifiers, promotion, drop, from })
+}
+
+#[wasm_bindgen]
+impl Board {
+    // 日本語の指し手を読み取り、当てはまる合法手を返す
+    // previous_to は直前の指し手の移動先（「同」に使う）
+    #[wasm_bindgen]
+    pub fn parse_move_text(&self, text: &str, previous_to: Option<Position>) -> MoveTextResult {
+        let Some(parsed) = parse_move_text(text) else {
+            return MoveTextResult::error(format!("指し手を読み取れません: {}", text));
+        };
+        if parsed.player.is_some_and(|player| player != self.current_player) {
+            return MoveTextResult::error("手番が違います".to_string());
+        }
+        let Some(to) = parsed.to.or(previous_to) else {
+            return MoveTextResult::error("直前の指し手がないため「同」は使えません".to_string());
+        };
+
+        let us = self.current_player;
+        let mut candidates: Vec<Move> = self
+            .generate_legal_moves()
+            .into_iter()
+            .filter(|mv| mv.to == to && mv.piece == parsed.piece)
+            .filter(|mv| parsed.from.is_none_or(|from| mv.from == from && !mv.is_drop()))
+            .filter(|mv| match parsed.promotion {
+                Promotion::Promote => mv.promote,
+                Promotion::NoPromote => !mv.promote && !mv.is_drop(),
+                Promotion::Unspecified => true,
+            })
+            .collect();
+
+        // 「打」がなければ盤上の駒を動かす手を優先する
+        if parsed.drop {
+            candidates.retain(|mv| mv.is_drop());
+        } else if candidates.iter().any(|mv| !mv.is_drop()) {
+            candidates.retain(|mv| !mv.is_drop());
+        }
+
+        // 動きの向き（先手から見て上が前）
+        let forward = |mv: &Move| if us == Player::Black { mv.to.row - mv.from.row } else { mv.from.row - mv.to.row };
+        for &modifier in &parsed.modifiers {
+            match modifier {
+                '上' => candidates.retain(|mv| forward(mv) > 0),
+                '引' => candidates.retain(|mv| forward(mv) < 0),
+                '寄' => candidates.retain(|mv| forward(mv) == 0),
+                '直' => candidates.retain(|mv| forward(mv) > 0 && mv.from.column == mv.to.column),
+                _ => {}
+            }
+        }
+        // 右・左は、残った候補のうち指す側から見て一番右・左にある駒
+        let rightness = |mv: &Move| if us == Player::Black { mv.from.column } else { -mv.from.column };
+        for &modifier in &parsed.modifiers {
+            let best = match modifier {
+                '右' => candidates.iter().map(rightness).max(),
+                '左' => candidates.iter().map(rightness).min(),
+                _ => continue,
+            };
+            candidates.retain(|mv| Some(rightness(mv)) == best);
+        }
+
+        if candidates.is_empty() {
+            return MoveTextResult::error(format!("当てはまる合法手がありません: {}", text));
+        }
+        MoveTextResult { candidates, error: None }
+    }
+}
@@ -0,0 +1,118 @@
+// 日本語表記（KIF形式などで使う駒名・マス名）
+
+use crate::bitboard::{position_of, square_of};
+use crate::{Move, Piece, Player};
+
+const FILE_CHARS: [char; 9] = ['１', '２', '３', '４', '５', '６', '７', '８', '９'];
+const RANK_CHARS: [char; 9] = ['一', '二', '三', '四', '五', '六', '七', '八', '九'];
+
+pub(crate) fn player_name(player: Player) -> &'static str {
+    match player {
+        Player::Black => "先手",
+        Player::White => "後手",
+    }
+}
+
+// KIF形式の駒名
+pub(crate) fn piece_name(piece: Piece) -> &'static str {
+    match piece {
+        Piece::Pawn => "歩",
+        Piece::Lance => "香",
+        Piece::Knight => "桂",
+        Piece::Silver => "銀",
+        Piece::Gold => "金",
+        Piece::Bishop => "角",
+        Piece::Rook => "飛",
+        Piece::King => "玉",
+        Piece::PromotedPawn => "と",
+        Piece::PromotedLance => "成香",
+        Piece::PromotedKnight => "成桂",
+        Piece::PromotedSilver => "成銀",
+        Piece::PromotedBishop => "馬",
+        Piece::PromotedRook => "龍",
+        Piece::Empty => "",
+    }
+}
+
+// 局面図で使う1文字の駒名（成香・成桂・成銀は杏・圭・全）
+pub(crate) fn piece_char(piece: Piece) -> char {
+    match piece {
+        Piece::PromotedLance => '杏',
+        Piece::PromotedKnight => '圭',
+        Piece::PromotedSilver => '全',
+        _ => piece_name(piece).chars().next().unwrap_or('・'),
+    }
+}
+
+// マスの表記（例: "５五"）
+pub(crate) fn square_name(sq: usize) -> String {
+    let pos = position_of(sq);
+    format!("{}{}", FILE_CHARS[(8 - pos.column) as usize], RANK_CHARS[(8 - pos.row) as usize])
+}
+
+// KIF形式の指し手（例: "７六歩(77)"、"同　銀(31)"、"５五角打"、"２二角成(88)"）
+// same は直前の指し手と同じマスに指す場合
+pub(crate) fn kif_move(mv: Move, same: bool) -> String {
+    let to = if same { "同　".to_string() } else { square_name(square_of(mv.to)) };
+    if mv.is_drop() {
+        return format!("{}{}打", to, piece_name(mv.piece));
+    }
+    format!(
+        "{}{}{}({}{})",
+        to,
+        piece_name(mv.piece),
+        if mv.promote { "成" } else { "" },
+        9 - mv.from.column,
+        9 - mv.from.row
+    )
+}
+
+// 筋の数字（半角・全角）を 1〜9 にする
+pub(crate) fn parse_file_char(c: char) -> Option<u32> {
+    FILE_CHARS.iter().position(|&f| f == c).map(|i| i as u32 + 1).or_else(|| c.to_digit(10)).filter(|n| (1..=9).contains(n))
+}
+
+// 段（漢数字・半角・全角の数字）を 1〜9 にする
+pub(crate) fn parse_rank_char(c: char) -> Option<u32> {
+    RANK_CHARS.iter().position(|&r| r == c).map(|i| i as u32 + 1).or_else(|| parse_file_char(c))
+}
+
+// 漢数字（1〜99、持ち駒の枚数などに使う）
+pub(crate) fn kanji_number(n: u32) -> String {
+    let digit = |d: u32| RANK_CHARS[(d - 1) as usize];
+    let mut result = String::new();
+    if n >= 20 {
+        result.push(digit(n / 10));
+    }
+    if n >= 10 {
+        result.push('十');
+    }
+    let ones = n % 10;
+    if ones > 0 {
+        result.push(digit(ones));
+    }
+    result
+}
+
+// 漢数字を数に戻す（"十八" → 18）
+pub(crate) fn parse_kanji_number(s: &str) -> Option<u32> {
+    let value = |c: char| RANK_CHARS.iter().position(|&r| r == c).map(|i| i as u32 + 1);
+    let chars: Vec<char> = s.chars().collect();
+    match chars.iter().position(|&c| c == '十') {
+        Some(i) => {
+            let tens = match i {
+                0 => 1,
+                1 => value(chars[0])?,
+                _ => return None,
+            };
+            let ones = match chars.len() - i - 1 {
+                0 => 0,
+                1 => value(chars[i + 1])?,
+                _ => return None,
+            };
+            Some(tens * 10 + ones)
+        }
+        None if chars.len() == 1 => value(chars[0]),
+        None => None,
+    }
+}
@@ -0,0 +1,258 @@
+// 盤面OCRの精度評価
+//
+// 読み取った局面と正解の局面を1組ずつ add() で加えていき、
+// マスごとの取り違え（駒の種類・先後・成り）、持ち駒の枚数、手番の誤りと、局面全体の完全一致率を集計する
+
+use std::fmt::Write;
+
+use wasm_bindgen::prelude::*;
+
+use crate::movegen::{ALL_PIECES, HAND_PIECES};
+use crate::notation::piece_name;
+use crate::{Board, Piece, Player};
+
+// マスの状態の種類（空き + 先後2 × 駒14）
+const CLASSES: usize = 29;
+
+fn class_of(piece: Piece, player: Player) -> usize {
+    if piece == Piece::Empty {
+        0
+    } else {
+        1 + player as usize * 14 + (piece as usize - 1)
+    }
+}
+
+fn class_name(class: usize) -> String {
+    if class == 0 {
+        return "空き".to_string();
+    }
+    let player = if class <= 14 { Player::Black } else { Player::White };
+    let piece = ALL_PIECES[1 + (class - 1) % 14];
+    format!("{}{}", if player == Player::Black { '▲' } else { '△' }, piece_name(piece))
+}
+
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct OcrEvaluation {
+    positions: u32,
+    exact_matches: u32,
+    squares: u32,
+    square_errors: u32,
+    type_errors: u32,      // 駒の種類の誤り（成り・不成の違いを除く）
+    side_errors: u32,      // 駒は合っているが先後が違う
+    promotion_errors: u32, // 駒の種類と先後は合っているが成り・不成が違う
+    missing_pieces: u32,   // 駒があるのに空きと読んだ
+    extra_pieces: u32,     // 空きなのに駒があると読んだ
+    hand_errors: u32,      // 持ち駒の枚数の差の合計
+    hand_error_positions: u32,
+    turn_errors: u32,
+    confusion: Vec<u32>, // [正解][読み取り]
+}
+
+impl Default for OcrEvaluation {
+    fn default() -> OcrEvaluation {
+        OcrEvaluation {
+            positions: 0,
+            exact_matches: 0,
+            squares: 0,
+            square_errors: 0,
+            type_errors: 0,
+            side_errors: 0,
+            promotion_errors: 0,
+            missing_pieces: 0,
+            extra_pieces: 0,
+            hand_errors: 0,
+            hand_error_positions: 0,
+            turn_errors: 0,
+            confusion: vec![0; CLASSES * CLASSES],
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl OcrEvaluation {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> OcrEvaluation {
+        OcrEvaluation::default()
+    }
+
+    // 読み取った局面と正解の局面を1組加える
+    #[wasm_bindgen]
+    pub fn add(&mut self, recognized: &Board, truth: &Board) {
+        self.positions += 1;
+        let mut exact = true;
+
+        for (&(got, got_player), &(expected, expected_player)) in recognized.pieces.iter().zip(truth.pieces.iter()) {
+            self.squares += 1;
+            self.confusion[class_of(expected, expected_player) * CLASSES + class_of(got, got_player)] += 1;
+            if got == Piece::Empty && expected == Piece::Empty {
+                continue;
+            }
+            if got == expected && got_player == expected_player {
+                continue;
+            }
+            exact = false;
+            self.square_errors += 1;
+            if expected == Piece::Empty {
+                self.extra_pieces += 1;
+            } else if got == Piece::Empty {
+                self.missing_pieces += 1;
+            } else if truth.get_original_piece(expected) != truth.get_original_piece(got) {
+                self.type_errors += 1;
+            } else if got_player != expected_player {
+                self.side_errors += 1;
+            } else {
+                self.promotion_errors += 1;
+            }
+        }
+
+        let mut hand_error = 0;
+        for player in [Player::Black, Player::White] {
+            for piece in HAND_PIECES {
+                let diff = recognized.get_captured_piece_count(player, piece) - truth.get_captured_piece_count(player, piece);
+                hand_error += diff.unsigned_abs();
+            }
+        }
+        if hand_error > 0 {
+            exact = false;
+            self.hand_errors += hand_error;
+            self.hand_error_positions += 1;
+        }
+
+        if recognized.current_player != truth.current_player {
+            exact = false;
+            self.turn_errors += 1;
+        }
+        if exact {
+            self.exact_matches += 1;
+        }
+    }
+
+    // 読み取れなかった（JSONが壊れているなど）局面を、完全一致しなかった局面として数える
+    #[wasm_bindgen]
+    pub fn add_failure(&mut self) {
+        self.positions += 1;
+    }
+
+    #[wasm_bindgen]
+    pub fn get_positions(&self) -> u32 {
+        self.positions
+    }
+
+    #[wasm_bindgen]
+    pub fn get_exact_matches(&self) -> u32 {
+        self.exact_matches
+    }
+
+    #[wasm_bindgen]
+    pub fn exact_match_rate(&self) -> f64 {
+        ratio(self.exact_matches, self.positions)
+    }
+
+    // マスの正解率（読み取れなかった局面のマスは含まない）
+    #[wasm_bindgen]
+    pub fn square_accuracy(&self) -> f64 {
+        ratio(self.squares - self.square_errors, self.squares)
+    }
+
+    #[wasm_bindgen]
+    pub fn get_square_errors(&self) -> u32 {
+        self.square_errors
+    }
+
+    #[wasm_bindgen]
+    pub fn get_type_errors(&self) -> u32 {
+        self.type_errors
+    }
+
+    #[wasm_bindgen]
+    pub fn get_side_errors(&self) -> u32 {
+        self.side_errors
+    }
+
+    #[wasm_bindgen]
+    pub fn get_promotion_errors(&self) -> u32 {
+        self.promotion_errors
+    }
+
+    #[wasm_bindgen]
+    pub fn get_missing_pieces(&self) -> u32 {
+        self.missing_pieces
+    }
+
+    #[wasm_bindgen]
+    pub fn get_extra_pieces(&self) -> u32 {
+        self.extra_pieces
+    }
+
+    #[wasm_bindgen]
+    pub fn get_hand_errors(&self) -> u32 {
+        self.hand_errors
+    }
+
+    #[wasm_bindgen]
+    pub fn get_hand_error_positions(&self) -> u32 {
+        self.hand_error_positions
+    }
+
+    #[wasm_bindgen]
+    pub fn get_turn_errors(&self) -> u32 {
+        self.turn_errors
+    }
+
+    // 正解が (expected, expected_player) のマスを (got, got_player) と読んだ数
+    #[wasm_bindgen]
+    pub fn get_confusion(&self, expected: Piece, expected_player: Player, got: Piece, got_player: Player) -> u32 {
+        self.confusion[class_of(expected, expected_player) * CLASSES + class_of(got, got_player)]
+    }
+
+    // 集計結果の文章（多い取り違えを上位 max_confusions 件まで載せる）
+    #[wasm_bindgen]
+    pub fn report(&self, max_confusions: usize) -> String {
+        let mut text = String::new();
+        let _ = writeln!(
+            text,
+            "局面数: {}  完全一致: {} ({:.1}%)",
+            self.positions,
+            self.exact_matches,
+            self.exact_match_rate() * 100.0
+        );
+        let _ = writeln!(
+            text,
+            "マス正解率: {:.2}%  （誤り {}: 種類 {} / 先後 {} / 成り {} / 見落とし {} / 余分 {}）",
+            self.square_accuracy() * 100.0,
+            self.square_errors,
+            self.type_errors,
+            self.side_errors,
+            self.promotion_errors,
+            self.missing_pieces,
+            self.extra_pieces
+        );
+        let _ = writeln!(text, "持ち駒の誤り: {}枚（{}局面）", self.hand_errors, self.hand_error_positions);
+        let _ = writeln!(text, "手番の誤り: {}局面", self.turn_errors);
+
+        let mut confusions: Vec<(usize, usize, u32)> = (0..CLASSES)
+            .flat_map(|expected| (0..CLASSES).map(move |got| (expected, got)))
+            .filter(|(expected, got)| expected != got)
+            .map(|(expected, got)| (expected, got, self.confusion[expected * CLASSES + got]))
+            .filter(|&(_, _, count)| count > 0)
+            .collect();
+        confusions.sort_by(|a, b| b.2.cmp(&a.2).then((a.0, a.1).cmp(&(b.0, b.1))));
+        if !confusions.is_empty() {
+            let _ = writeln!(text, "多い取り違え（正解 → 読み取り）:");
+        }
+        for (expected, got, count) in confusions.into_iter().take(max_confusions) {
+            let _ = writeln!(text, "  {} → {}: {}", class_name(expected), class_name(got), count);
+        }
+        text
+    }
+}
+
+fn ratio(numerator: u32, denominator: u32) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
@@ -0,0 +1,162 @@
+// 指した手の結果（取った駒・成り・王手・対局の状態・棋譜の表記）
+//
+// フロントエンドが指す前後の盤面を比べなくても、駒を取る動きや効果音を決められるようにする
+
+use wasm_bindgen::prelude::*;
+
+use crate::bitboard::square_of;
+use crate::notation::kif_move;
+use crate::{Board, GameStatus, Move, Piece, Player, Position};
+
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct MoveOutcome {
+    mv: Move, // 指した手（mv.piece は動かす前の駒）
+    player: Player,
+    placed_piece: Piece,   // 移動先に置かれた駒（成った場合は成った後の駒）
+    captured_piece: Piece, // 取った駒（成り駒は成ったまま。取っていない場合はEmpty）
+    hand_piece: Piece,     // 持ち駒に加わった駒（成り駒は元の駒。玉を取った場合と取っていない場合はEmpty）
+    gives_check: bool,
+    status: GameStatus,
+    winner: Option<Player>,
+}
+
+#[wasm_bindgen]
+impl MoveOutcome {
+    #[wasm_bindgen]
+    pub fn get_move(&self) -> Move {
+        self.mv
+    }
+
+    // 指した側
+    #[wasm_bindgen]
+    pub fn get_player(&self) -> Player {
+        self.player
+    }
+
+    #[wasm_bindgen]
+    pub fn get_from(&self) -> Position {
+        self.mv.from
+    }
+
+    #[wasm_bindgen]
+    pub fn get_to(&self) -> Position {
+        self.mv.to
+    }
+
+    // 動かした駒（動かす前）
+    #[wasm_bindgen]
+    pub fn get_piece(&self) -> Piece {
+        self.mv.piece
+    }
+
+    #[wasm_bindgen]
+    pub fn get_placed_piece(&self) -> Piece {
+        self.placed_piece
+    }
+
+    #[wasm_bindgen]
+    pub fn is_drop(&self) -> bool {
+        self.mv.is_drop()
+    }
+
+    #[wasm_bindgen]
+    pub fn is_promotion(&self) -> bool {
+        self.mv.promote
+    }
+
+    #[wasm_bindgen]
+    pub fn is_capture(&self) -> bool {
+        self.captured_piece != Piece::Empty
+    }
+
+    #[wasm_bindgen]
+    pub fn get_captured_piece(&self) -> Piece {
+        self.captured_piece
+    }
+
+    #[wasm_bindgen]
+    pub fn get_hand_piece(&self) -> Piece {
+        self.hand_piece
+    }
+
+    #[wasm_bindgen]
+    pub fn is_check(&self) -> bool {
+        self.gives_check
+    }
+
+    // 指した後の対局の状態
+    #[wasm_bindgen]
+    pub fn get_status(&self) -> GameStatus {
+        self.status
+    }
+
+    #[wasm_bindgen]
+    pub fn get_winner(&self) -> Option<Player> {
+        self.winner
+    }
+
+    #[wasm_bindgen]
+    pub fn get_usi(&self) -> String {
+        self.mv.to_usi()
+    }
+
+    // KIF形式の表記（previous_to が移動先と同じなら「同」にする）
+    #[wasm_bindgen]
+    pub fn get_kif(&self, previous_to: Option<Position>) -> String {
+        kif_move(self.mv, previous_to == Some(self.mv.to))
+    }
+}
+
+#[wasm_bindgen]
+impl Board {
+    // make_move_with_promotion と同じく駒を動かし、結果を返す（指せない場合は None）
+    #[wasm_bindgen]
+    pub fn make_move_with_outcome(&mut self, from: Position, to: Position, promote: bool) -> Option<MoveOutcome> {
+        if self.captured_king.is_some() || !self.is_valid_move(from, to) {
+            return None;
+        }
+        let (piece, _) = self.get_piece_at(from)?;
+        let promote = promote && self.can_promote(from.row, from.column, to.row, to.column);
+        self.apply_move(Move::new(from, to, piece, promote))
+    }
+
+    // drop_piece と同じく持ち駒を打ち、結果を返す（打てない場合は None）
+    #[wasm_bindgen]
+    pub fn drop_piece_with_outcome(&mut self, piece: Piece, to_row: i32, to_col: i32) -> Option<MoveOutcome> {
+        if self.captured_king.is_some() || !self.can_drop_piece(piece, to_row, to_col) {
+            return None;
+        }
+        if self.get_captured_piece_count(self.current_player, piece) <= 0 {
+            return None;
+        }
+        self.apply_move(Move::new_drop(piece, Position::new(to_row, to_col)))
+    }
+}
+
+impl Board {
+    // 合法手なら指して結果を返す（自玉が取られる手・打ち歩詰めは指せない）
+    fn apply_move(&mut self, mv: Move) -> Option<MoveOutcome> {
+        if !self.generate_legal_moves().contains(&mv) {
+            return None;
+        }
+        let player = self.current_player;
+        let undo = self.do_move(mv);
+        let captured_piece = undo.get_captured_piece();
+        let hand_piece = match captured_piece {
+            Piece::King => Piece::Empty,
+            piece => self.get_original_piece(piece),
+        };
+        let status = self.game_status();
+        Some(MoveOutcome {
+            mv,
+            player,
+            placed_piece: self.pieces[square_of(mv.to)].0,
+            captured_piece,
+            hand_piece,
+            gives_check: self.is_in_check(player.opponent()),
+            status,
+            winner: self.winner_of(status),
+        })
+    }
+}
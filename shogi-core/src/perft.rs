@@ -0,0 +1,50 @@
+use wasm_bindgen::prelude::*;
+
+use crate::Board;
+
+#[wasm_bindgen]
+impl Board {
+    // 合法手の木の末端局面数を数える（成・不成の選択と持ち駒を打つ手を含む）
+    #[wasm_bindgen]
+    pub fn perft(&self, depth: u32) -> u64 {
+        let mut board = self.clone();
+        board.perft_recursive(depth)
+    }
+
+    // 1手目ごとの末端局面数を "7g7f: 30" の形式で返す
+    #[wasm_bindgen]
+    pub fn divide(&self, depth: u32) -> Vec<String> {
+        if depth == 0 {
+            return Vec::new();
+        }
+        self.generate_legal_moves()
+            .into_iter()
+            .map(|mv| {
+                let mut board = self.clone();
+                board.do_move(mv);
+                format!("{}: {}", mv.to_usi(), board.perft_recursive(depth - 1))
+            })
+            .collect()
+    }
+}
+
+impl Board {
+    fn perft_recursive(&mut self, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        let mut moves = Vec::with_capacity(128);
+        self.generate_moves(&mut moves);
+        // 最後の1手は局面を進めずに手の数だけ数える
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+        let mut nodes = 0;
+        for mv in moves {
+            let undo = self.do_move(mv);
+            nodes += self.perft_recursive(depth - 1);
+            self.undo_move(mv, undo);
+        }
+        nodes
+    }
+}
@@ -0,0 +1,247 @@
+// 駒箱を使った局面編集
+//
+// 1組40枚の駒のうち、盤上にも持ち駒にもない駒が駒箱に入っているものとして扱う。
+// edit_ で始まる操作は駒を盤・持ち駒・駒箱の間で移すだけなので、駒の総数は変わらない
+
+use wasm_bindgen::prelude::*;
+
+use crate::bitboard::square;
+use crate::movegen::HAND_PIECES;
+use crate::validate::PIECE_LIMITS;
+use crate::{Board, Piece, Player};
+
+// 1組の駒の枚数（歩香桂銀金角飛玉の順）。玉以外は局面の検証と同じ PIECE_LIMITS から作る
+const FULL_SET: [i32; 8] = {
+    let mut set = [0; 8];
+    set[7] = 2; // 玉
+    let mut i = 0;
+    while i < PIECE_LIMITS.len() {
+        match box_index(PIECE_LIMITS[i].0) {
+            Some(index) => set[index] = PIECE_LIMITS[i].1,
+            None => panic!("PIECE_LIMITS に駒箱に入らない駒があります"),
+        }
+        i += 1;
+    }
+    set
+};
+
+const fn box_index(piece: Piece) -> Option<usize> {
+    match piece {
+        Piece::Pawn | Piece::PromotedPawn => Some(0),
+        Piece::Lance | Piece::PromotedLance => Some(1),
+        Piece::Knight | Piece::PromotedKnight => Some(2),
+        Piece::Silver | Piece::PromotedSilver => Some(3),
+        Piece::Gold => Some(4),
+        Piece::Bishop | Piece::PromotedBishop => Some(5),
+        Piece::Rook | Piece::PromotedRook => Some(6),
+        Piece::King => Some(7),
+        Piece::Empty => None,
+    }
+}
+
+fn in_range(row: i32, col: i32) -> bool {
+    (0..9).contains(&row) && (0..9).contains(&col)
+}
+
+#[wasm_bindgen]
+impl Board {
+    // 駒箱に残っている駒の枚数（歩香桂銀金角飛玉の順）
+    // 1組より多い駒が盤上・持ち駒にある場合は負の値になる
+    #[wasm_bindgen]
+    pub fn remaining_in_box(&self) -> Vec<i32> {
+        let mut remaining = FULL_SET;
+        for sq in self.occupied() {
+            if let Some(i) = box_index(self.pieces[sq].0) {
+                remaining[i] -= 1;
+            }
+        }
+        for player in [Player::Black, Player::White] {
+            for (i, piece) in HAND_PIECES.into_iter().enumerate() {
+                remaining[i] -= self.get_captured_piece_count(player, piece);
+            }
+        }
+        remaining.to_vec()
+    }
+
+    // 駒箱にある指定した駒の枚数（成り駒は元の駒として数える）
+    #[wasm_bindgen]
+    pub fn get_piece_box_count(&self, piece: Piece) -> i32 {
+        box_index(piece).map_or(0, |i| self.remaining_in_box()[i])
+    }
+
+    // 駒箱の駒を盤上の空いたマスに置く（成り駒を指定した場合は成った状態で置く）
+    #[wasm_bindgen]
+    pub fn edit_place_from_box(&mut self, row: i32, col: i32, piece: Piece, player: Player) -> bool {
+        if !in_range(row, col) || self.get_piece_box_count(piece) <= 0 {
+            return false;
+        }
+        let sq = square(row as usize, col as usize);
+        if self.pieces[sq].0 != Piece::Empty {
+            return false;
+        }
+        self.put_piece(sq, piece, player);
+        true
+    }
+
+    // 盤上の駒を別のマスへ移す。移動先に駒があれば、動かした駒の持ち主の持ち駒にする（玉は駒箱へ）
+    #[wasm_bindgen]
+    pub fn edit_move_on_board(&mut self, from_row: i32, from_col: i32, to_row: i32, to_col: i32) -> bool {
+        if !in_range(from_row, from_col) || !in_range(to_row, to_col) {
+            return false;
+        }
+        let from = square(from_row as usize, from_col as usize);
+        let to = square(to_row as usize, to_col as usize);
+        let (piece, player) = self.pieces[from];
+        if piece == Piece::Empty || from == to {
+            return false;
+        }
+        let (target, _) = self.pieces[to];
+        if target != Piece::Empty && target != Piece::King {
+            let original = self.get_original_piece(target);
+            self.add_captured_piece(player, original);
+        }
+        self.remove_piece(from);
+        self.put_piece(to, piece, player);
+        true
+    }
+
+    // 盤上の駒を持ち駒にする（玉は持ち駒にできない）
+    #[wasm_bindgen]
+    pub fn edit_board_to_hand(&mut self, row: i32, col: i32, player: Player) -> bool {
+        if !in_range(row, col) {
+            return false;
+        }
+        let sq = square(row as usize, col as usize);
+        let (piece, _) = self.pieces[sq];
+        if piece == Piece::Empty || piece == Piece::King {
+            return false;
+        }
+        let original = self.get_original_piece(piece);
+        self.remove_piece(sq);
+        self.add_captured_piece(player, original);
+        true
+    }
+
+    // 盤上の駒を駒箱に戻す
+    #[wasm_bindgen]
+    pub fn edit_board_to_box(&mut self, row: i32, col: i32) -> bool {
+        if !in_range(row, col) {
+            return false;
+        }
+        let sq = square(row as usize, col as usize);
+        if self.pieces[sq].0 == Piece::Empty {
+            return false;
+        }
+        self.remove_piece(sq);
+        true
+    }
+
+    // 持ち駒を盤上の空いたマスに、持ち主の駒として置く
+    #[wasm_bindgen]
+    pub fn edit_hand_to_board(&mut self, player: Player, piece: Piece, row: i32, col: i32) -> bool {
+        if !in_range(row, col) || self.get_captured_piece_count(player, piece) <= 0 {
+            return false;
+        }
+        let sq = square(row as usize, col as usize);
+        if self.pieces[sq].0 != Piece::Empty {
+            return false;
+        }
+        self.use_captured_piece(player, piece);
+        self.put_piece(sq, piece, player);
+        true
+    }
+
+    // 持ち駒を相手の持ち駒に移す
+    #[wasm_bindgen]
+    pub fn edit_hand_to_hand(&mut self, from: Player, to: Player, piece: Piece) -> bool {
+        if from == to || !self.use_captured_piece(from, piece) {
+            return false;
+        }
+        self.add_captured_piece(to, piece);
+        true
+    }
+
+    // 持ち駒を駒箱に戻す
+    #[wasm_bindgen]
+    pub fn edit_hand_to_box(&mut self, player: Player, piece: Piece) -> bool {
+        self.use_captured_piece(player, piece)
+    }
+
+    // 駒箱の駒を持ち駒にする
+    #[wasm_bindgen]
+    pub fn edit_box_to_hand(&mut self, piece: Piece, player: Player) -> bool {
+        let original = self.get_original_piece(piece);
+        if original == Piece::King || self.get_piece_box_count(original) <= 0 {
+            return false;
+        }
+        self.add_captured_piece(player, original);
+        true
+    }
+
+    // 盤上の駒の成・不成を切り替える（成れない駒は変わらない）
+    #[wasm_bindgen]
+    pub fn edit_flip_promotion(&mut self, row: i32, col: i32) -> bool {
+        if !in_range(row, col) {
+            return false;
+        }
+        let sq = square(row as usize, col as usize);
+        let (piece, player) = self.pieces[sq];
+        let flipped = if self.is_promoted_piece(piece) {
+            self.get_original_piece(piece)
+        } else {
+            match self.get_promoted_piece(piece) {
+                Some(promoted) => promoted,
+                None => return false,
+            }
+        };
+        self.put_piece(sq, flipped, player);
+        true
+    }
+
+    // 盤上の駒の先後を入れ替える
+    #[wasm_bindgen]
+    pub fn edit_flip_side(&mut self, row: i32, col: i32) -> bool {
+        if !in_range(row, col) {
+            return false;
+        }
+        let sq = square(row as usize, col as usize);
+        let (piece, player) = self.pieces[sq];
+        if piece == Piece::Empty {
+            return false;
+        }
+        self.put_piece(sq, piece, player.opponent());
+        true
+    }
+
+    // 盤上の駒を 先手 → 先手成り → 後手 → 後手成り の順に切り替える（成れない駒は先後だけ）
+    #[wasm_bindgen]
+    pub fn edit_cycle_piece(&mut self, row: i32, col: i32) -> bool {
+        if !in_range(row, col) {
+            return false;
+        }
+        let (piece, _) = self.pieces[square(row as usize, col as usize)];
+        if piece == Piece::Empty {
+            return false;
+        }
+        if self.is_promoted_piece(piece) || self.get_promoted_piece(piece).is_none() {
+            if self.is_promoted_piece(piece) {
+                self.edit_flip_promotion(row, col);
+            }
+            self.edit_flip_side(row, col)
+        } else {
+            self.edit_flip_promotion(row, col)
+        }
+    }
+
+    // 駒箱に残っている玉以外の駒をすべて持ち駒にする（詰将棋の「残り駒全部」）
+    #[wasm_bindgen]
+    pub fn edit_send_box_to_hand(&mut self, player: Player) {
+        let remaining = self.remaining_in_box();
+        for (i, piece) in HAND_PIECES.into_iter().enumerate() {
+            if remaining[i] > 0 {
+                let count = self.get_captured_piece_count(player, piece);
+                self.set_captured_piece_count(player, piece, count + remaining[i]);
+            }
+        }
+    }
+}
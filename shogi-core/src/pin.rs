@@ -0,0 +1,83 @@
+// ピン（素抜きの筋にいて動けない駒）と、動くと飛び駒の利きが通る駒（開き王手・開き攻撃）
+
+use wasm_bindgen::prelude::*;
+
+use crate::bitboard::{position_of, tables, Bitboard};
+use crate::{Board, Piece, Player, Position};
+
+// 飛び駒（香・角・飛・馬・龍）の利きを1枚だけで遮っている駒
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct BlockedLine {
+    blocker: usize, // 遮っている駒のマス
+    slider: usize,  // 飛び駒のマス
+    target: usize,  // 遮っている駒がどくと利きが通るマス（ピンなら玉）
+}
+
+#[wasm_bindgen]
+impl BlockedLine {
+    #[wasm_bindgen]
+    pub fn get_blocker(&self) -> Position {
+        position_of(self.blocker)
+    }
+
+    #[wasm_bindgen]
+    pub fn get_slider(&self) -> Position {
+        position_of(self.slider)
+    }
+
+    #[wasm_bindgen]
+    pub fn get_target(&self) -> Position {
+        position_of(self.target)
+    }
+
+    // 飛び駒から目標のマスまでの筋（飛び駒のマスを含み、目標のマスは含まない）
+    #[wasm_bindgen]
+    pub fn get_line(&self) -> Vec<Position> {
+        let line = tables().between(self.slider, self.target) | Bitboard::from_square(self.slider);
+        line.map(position_of).collect()
+    }
+}
+
+#[wasm_bindgen]
+impl Board {
+    // player 側の駒のうち、相手の飛び駒に玉へのピンをされている駒
+    #[wasm_bindgen]
+    pub fn pinned_pieces(&self, player: Player) -> Vec<BlockedLine> {
+        let kings = self.pieces_of(Piece::King, player);
+        self.blocked_lines(player.opponent(), kings)
+            .into_iter()
+            .filter(|line| self.pieces[line.blocker].1 == player)
+            .collect()
+    }
+
+    // player 側の駒のうち、動くと自分の飛び駒の利きが相手の駒に通る駒（目標が玉なら開き王手）
+    #[wasm_bindgen]
+    pub fn discovered_attacks(&self, player: Player) -> Vec<BlockedLine> {
+        self.blocked_lines(player, self.by_player[player.opponent() as usize])
+            .into_iter()
+            .filter(|line| self.pieces[line.blocker].1 == player)
+            .collect()
+    }
+}
+
+impl Board {
+    // owner 側の飛び駒から targets のマスまでの間に、駒がちょうど1枚ある筋
+    fn blocked_lines(&self, owner: Player, targets: Bitboard) -> Vec<BlockedLine> {
+        let t = tables();
+        let occupied = self.occupied();
+        let mut lines = Vec::new();
+        for piece in [Piece::Lance, Piece::Bishop, Piece::Rook, Piece::PromotedBishop, Piece::PromotedRook] {
+            for slider in self.pieces_of(piece, owner) {
+                // 駒がないものとした利き（馬・龍の1マスの利きは間に駒が入らないので除かれる）
+                for target in t.attacks(piece, owner, slider, Bitboard::EMPTY) & targets {
+                    let between = t.between(slider, target) & occupied;
+                    if between.count() == 1 {
+                        lines.push(BlockedLine { blocker: between.lsb(), slider, target });
+                    }
+                }
+            }
+        }
+        lines
+    }
+}
@@ -0,0 +1,109 @@
+// 駒の取り合いの損得（SEE: 1つのマスで取って取り返してを続けた結果）と浮き駒の検出
+//
+// 初心者向けに「角が取られそうです」と知らせる機能や、探索での指し手の並べ替えに使う
+
+use wasm_bindgen::prelude::*;
+
+use crate::bitboard::{position_of, square_of};
+use crate::{Board, Move, Piece, Player, Position};
+
+// 駒の価値（歩を100とした目安）
+fn piece_value(piece: Piece) -> i32 {
+    match piece {
+        Piece::Empty => 0,
+        Piece::Pawn => 100,
+        Piece::Lance => 350,
+        Piece::Knight => 450,
+        Piece::Silver => 550,
+        Piece::Gold => 600,
+        Piece::Bishop => 950,
+        Piece::Rook => 1100,
+        Piece::King => 15000,
+        Piece::PromotedPawn | Piece::PromotedLance | Piece::PromotedKnight | Piece::PromotedSilver => 600,
+        Piece::PromotedBishop => 1050,
+        Piece::PromotedRook => 1550,
+    }
+}
+
+#[wasm_bindgen]
+impl Board {
+    // 指し手の移動先で駒の取り合いを続けたときの、指す側から見た駒の価値の増減
+    // どちらも損になる取り返しはしないものとし、成れる場合は成る。ピンは考えない
+    #[wasm_bindgen]
+    pub fn see(&self, mv: Move) -> i32 {
+        if !mv.to.is_valid() {
+            return 0;
+        }
+        let to = square_of(mv.to);
+        let mut occupied = self.occupied();
+        let (mut side, placed) = if mv.is_drop() {
+            (self.current_player, mv.piece)
+        } else {
+            let (piece, player) = self.pieces[square_of(mv.from)];
+            occupied.clear(square_of(mv.from));
+            let placed = if mv.promote { self.get_promoted_piece(piece).unwrap_or(piece) } else { piece };
+            (player, placed)
+        };
+
+        let mut gains = vec![piece_value(self.pieces[to].0) + piece_value(placed) - piece_value(mv.piece)];
+        let mut on_square = placed;
+        loop {
+            side = side.opponent();
+            let attackers = self.attackers_to_square(to, side, occupied) & occupied;
+            let Some(from) = attackers.min_by_key(|&sq| piece_value(self.pieces[sq].0)) else {
+                break;
+            };
+            let piece = self.pieces[from].0;
+            // 玉で取れるのは、取り返されない場合だけ
+            if piece == Piece::King {
+                let mut after = occupied;
+                after.clear(from);
+                if !(self.attackers_to_square(to, side.opponent(), after) & after).is_empty() {
+                    break;
+                }
+            }
+            let pos = position_of(from);
+            let promoted = if self.can_promote(pos.row, pos.column, mv.to.row, mv.to.column) {
+                self.get_promoted_piece(piece).unwrap_or(piece)
+            } else {
+                piece
+            };
+            let last = *gains.last().unwrap_or(&0);
+            gains.push(piece_value(on_square) + piece_value(promoted) - piece_value(piece) - last);
+            on_square = promoted;
+            occupied.clear(from);
+        }
+
+        // 後ろから、取り返すか取り返さないかの得な方を選んでいく
+        while gains.len() > 1 {
+            let last = gains.pop().unwrap_or(0);
+            if let Some(previous) = gains.last_mut() {
+                *previous = -(-*previous).max(last);
+            }
+        }
+        gains[0]
+    }
+
+    // player 側の浮き駒（相手の利きの数が味方の利きの数より多いか、自分より価値の低い駒に取られる駒。玉は除く）
+    #[wasm_bindgen]
+    pub fn hanging_pieces(&self, player: Player) -> Vec<Position> {
+        let occupied = self.occupied();
+        let mut hanging = Vec::new();
+        for sq in self.by_player[player as usize] {
+            let piece = self.pieces[sq].0;
+            if piece == Piece::King {
+                continue;
+            }
+            let attackers = self.attackers_to_square(sq, player.opponent(), occupied);
+            if attackers.is_empty() {
+                continue;
+            }
+            let defenders = self.attackers_to_square(sq, player, occupied);
+            let cheapest = attackers.map(|from| piece_value(self.pieces[from].0)).min().unwrap_or(0);
+            if attackers.count() > defenders.count() || cheapest < piece_value(piece) {
+                hanging.push(position_of(sq));
+            }
+        }
+        hanging
+    }
+}
@@ -0,0 +1,146 @@
+// 局面のJSON表現（serde フィーチャー）
+//
+// フロントエンドの RecognizedPosition と同じ形
+// { turn: "black", board: [[{ piece: "pawn", side: "white" } | null, ...], ...], captured: { black: {...}, white: {...} } }
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::bitboard::square;
+use crate::{Board, Piece, Player};
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct BoardRecord {
+    turn: Player,
+    board: Vec<Vec<Option<Cell>>>, // [段][筋]（set_piece_by_coords と同じ並び）
+    captured: Captured,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Cell {
+    piece: Piece,
+    side: Player,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Captured {
+    black: Hand,
+    white: Hand,
+}
+
+// 持ち駒の枚数（省略した駒は0枚）
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct Hand {
+    pawn: i32,
+    lance: i32,
+    knight: i32,
+    silver: i32,
+    gold: i32,
+    bishop: i32,
+    rook: i32,
+    king: i32, // 玉は持ち駒にできないので読み込み時は無視する（盤上にない玉は駒箱にある）
+}
+
+impl Hand {
+    fn from_board(board: &Board, player: Player) -> Hand {
+        Hand {
+            pawn: board.get_captured_piece_count(player, Piece::Pawn),
+            lance: board.get_captured_piece_count(player, Piece::Lance),
+            knight: board.get_captured_piece_count(player, Piece::Knight),
+            silver: board.get_captured_piece_count(player, Piece::Silver),
+            gold: board.get_captured_piece_count(player, Piece::Gold),
+            bishop: board.get_captured_piece_count(player, Piece::Bishop),
+            rook: board.get_captured_piece_count(player, Piece::Rook),
+            king: 0,
+        }
+    }
+
+    fn counts(&self) -> [(Piece, i32); 7] {
+        [
+            (Piece::Pawn, self.pawn),
+            (Piece::Lance, self.lance),
+            (Piece::Knight, self.knight),
+            (Piece::Silver, self.silver),
+            (Piece::Gold, self.gold),
+            (Piece::Bishop, self.bishop),
+            (Piece::Rook, self.rook),
+        ]
+    }
+}
+
+impl From<Board> for BoardRecord {
+    fn from(board: Board) -> BoardRecord {
+        let rows = (0..9)
+            .map(|row| {
+                (0..9)
+                    .map(|col| match board.pieces[square(row, col)] {
+                        (Piece::Empty, _) => None,
+                        (piece, side) => Some(Cell { piece, side }),
+                    })
+                    .collect()
+            })
+            .collect();
+        BoardRecord {
+            turn: board.current_player,
+            board: rows,
+            captured: Captured {
+                black: Hand::from_board(&board, Player::Black),
+                white: Hand::from_board(&board, Player::White),
+            },
+        }
+    }
+}
+
+impl TryFrom<BoardRecord> for Board {
+    type Error = String;
+
+    fn try_from(record: BoardRecord) -> Result<Board, String> {
+        if record.board.len() != 9 {
+            return Err(format!("段の数が不正です: {}", record.board.len()));
+        }
+
+        let mut board = Board::new();
+        board.clear_board();
+        board.clear_captured_pieces(Player::Black);
+        board.clear_captured_pieces(Player::White);
+
+        for (row, cells) in record.board.iter().enumerate() {
+            if cells.len() != 9 {
+                return Err(format!("{}段目のマス数が不正です", row + 1));
+            }
+            for (col, cell) in cells.iter().enumerate() {
+                if let Some(cell) = cell {
+                    board.put_piece(square(row, col), cell.piece, cell.side);
+                }
+            }
+        }
+
+        for (player, hand) in [(Player::Black, &record.captured.black), (Player::White, &record.captured.white)] {
+            for (piece, count) in hand.counts() {
+                if !board.set_captured_piece_count(player, piece, count) {
+                    return Err(format!("持ち駒の枚数が不正です: {}", count));
+                }
+            }
+        }
+        board.set_current_player(record.turn);
+
+        Ok(board)
+    }
+}
+
+#[wasm_bindgen]
+impl Board {
+    // RecognizedPosition と同じ形のJSオブジェクトに変換する
+    #[wasm_bindgen]
+    pub fn to_js_value(&self) -> Result<JsValue, String> {
+        let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+        self.serialize(&serializer).map_err(|e| e.to_string())
+    }
+
+    // RecognizedPosition と同じ形のJSオブジェクトから盤面を作成する
+    #[wasm_bindgen]
+    pub fn from_js_value(value: JsValue) -> Result<Board, String> {
+        serde_wasm_bindgen::from_value(value).map_err(|e| e.to_string())
+    }
+}
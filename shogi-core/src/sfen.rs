@@ -0,0 +1,243 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{Board, Move, Piece, Player, Position};
+
+// 持ち駒の表記順（飛角金銀桂香歩）
+const SFEN_HAND_ORDER: [Piece; 7] = [
+    Piece::Rook,
+    Piece::Bishop,
+    Piece::Gold,
+    Piece::Silver,
+    Piece::Knight,
+    Piece::Lance,
+    Piece::Pawn,
+];
+
+#[wasm_bindgen]
+impl Board {
+    // SFEN文字列（"position sfen" の後ろの部分）から盤面を作成する
+    #[wasm_bindgen]
+    pub fn from_sfen(sfen: &str) -> Result<Board, String> {
+        let mut fields = sfen.split_whitespace();
+        let board_field = fields.next().ok_or("SFENが空です")?;
+        let turn_field = fields.next().unwrap_or("b");
+        let hand_field = fields.next().unwrap_or("-");
+
+        let mut board = Board::new();
+        board.clear_board();
+        board.clear_captured_pieces(Player::Black);
+        board.clear_captured_pieces(Player::White);
+
+        let ranks: Vec<&str> = board_field.split('/').collect();
+        if ranks.len() != 9 {
+            return Err(format!("段の数が不正です: {}", ranks.len()));
+        }
+        for (i, rank) in ranks.iter().enumerate() {
+            // SFENは一段目（後手側）から、各段は9筋から書かれる
+            let row = 8 - i as i32;
+            let mut col = 0;
+            let mut promoted = false;
+            for c in rank.chars() {
+                if let Some(n) = c.to_digit(10) {
+                    col += n as i32;
+                    continue;
+                }
+                if c == '+' {
+                    promoted = true;
+                    continue;
+                }
+                if col >= 9 {
+                    return Err(format!("{}段目の駒が多すぎます", i + 1));
+                }
+                let (piece, player) = piece_from_sfen_char(c).ok_or(format!("不明な駒: {}", c))?;
+                let piece = if promoted {
+                    board.get_promoted_piece(piece).ok_or(format!("成れない駒: +{}", c))?
+                } else {
+                    piece
+                };
+                board.set_piece(Position::new(row, col), piece, player);
+                promoted = false;
+                col += 1;
+            }
+            if col != 9 {
+                return Err(format!("{}段目のマス数が不正です", i + 1));
+            }
+        }
+
+        let player = match turn_field {
+            "b" => Player::Black,
+            "w" => Player::White,
+            _ => return Err(format!("手番が不正です: {}", turn_field)),
+        };
+        board.set_current_player(player);
+
+        if hand_field != "-" {
+            let mut count = 0;
+            for c in hand_field.chars() {
+                if let Some(n) = c.to_digit(10) {
+                    count = count * 10 + n as i32;
+                    continue;
+                }
+                let (piece, player) = piece_from_sfen_char(c).ok_or(format!("不明な持ち駒: {}", c))?;
+                if piece == Piece::King {
+                    return Err("玉は持ち駒にできません".to_string());
+                }
+                let current = board.get_captured_piece_count(player, piece);
+                board.set_captured_piece_count(player, piece, current + count.max(1));
+                count = 0;
+            }
+        }
+
+        Ok(board)
+    }
+
+    // 盤面をSFEN文字列に変換する（手数は1とする）
+    #[wasm_bindgen]
+    pub fn to_sfen(&self) -> String {
+        let mut ranks = Vec::new();
+        for row in (0..9).rev() {
+            let mut rank = String::new();
+            let mut empty = 0;
+            for col in 0..9 {
+                match self.get_piece_at(Position::new(row, col)) {
+                    Some((piece, player)) => {
+                        if empty > 0 {
+                            rank.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        rank.push_str(&piece_to_sfen(piece, player));
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                rank.push_str(&empty.to_string());
+            }
+            ranks.push(rank);
+        }
+
+        let turn = if self.current_player == Player::Black { "b" } else { "w" };
+
+        let mut hand = String::new();
+        for player in [Player::Black, Player::White] {
+            for piece in SFEN_HAND_ORDER {
+                let count = self.get_captured_piece_count(player, piece);
+                if count > 1 {
+                    hand.push_str(&count.to_string());
+                }
+                if count > 0 {
+                    hand.push_str(&piece_to_sfen(piece, player));
+                }
+            }
+        }
+        if hand.is_empty() {
+            hand.push('-');
+        }
+
+        format!("{} {} {} 1", ranks.join("/"), turn, hand)
+    }
+
+    // USI形式の指し手を Move にする（合法手かどうかは調べない）
+    #[wasm_bindgen]
+    pub fn move_from_usi(&self, usi: &str) -> Result<Move, String> {
+        let invalid = || format!("USIの指し手が不正です: {}", usi);
+        if let Some((letter, to)) = usi.split_once('*') {
+            let mut chars = letter.chars();
+            let (piece, _) = match (chars.next(), chars.next()) {
+                (Some(c), None) if c.is_ascii_uppercase() => piece_from_sfen_char(c).ok_or_else(invalid)?,
+                _ => return Err(invalid()),
+            };
+            let to = parse_usi_square(to).ok_or_else(invalid)?;
+            return Ok(Move::new_drop(piece, to));
+        }
+
+        let (squares, promote) = match usi.strip_suffix('+') {
+            Some(squares) => (squares, true),
+            None => (usi, false),
+        };
+        if squares.len() != 4 || !squares.is_ascii() {
+            return Err(invalid());
+        }
+        let from = parse_usi_square(&squares[..2]).ok_or_else(invalid)?;
+        let to = parse_usi_square(&squares[2..]).ok_or_else(invalid)?;
+        // 指した後の局面（直前の指し手の表示など）では移動先の駒を使う
+        let (piece, _) = self
+            .get_piece_at(from)
+            .or_else(|| self.get_piece_at(to))
+            .ok_or(format!("移動元に駒がありません: {}", usi))?;
+        Ok(Move::new(from, to, piece, promote))
+    }
+}
+
+#[wasm_bindgen]
+impl Move {
+    // USI形式の指し手（例: "7g7f", "2b3c+", "P*5e"）
+    #[wasm_bindgen]
+    pub fn to_usi(&self) -> String {
+        let to = usi_square(self.to);
+        if self.is_drop() {
+            let letter = piece_to_sfen(self.piece, Player::Black);
+            format!("{}*{}", letter, to)
+        } else {
+            format!("{}{}{}", usi_square(self.from), to, if self.promote { "+" } else { "" })
+        }
+    }
+}
+
+// マスのUSI表記（筋は数字、段は a-i）
+fn usi_square(pos: Position) -> String {
+    let file = 9 - pos.column;
+    let rank = (b'a' + (8 - pos.row) as u8) as char;
+    format!("{}{}", file, rank)
+}
+
+fn parse_usi_square(s: &str) -> Option<Position> {
+    let mut chars = s.chars();
+    let file = chars.next()?.to_digit(10).filter(|f| (1..=9).contains(f))? as i32;
+    let rank = chars.next().filter(|r| ('a'..='i').contains(r))? as i32 - 'a' as i32;
+    if chars.next().is_some() {
+        return None;
+    }
+    Some(Position::new(8 - rank, 9 - file))
+}
+
+fn piece_from_sfen_char(c: char) -> Option<(Piece, Player)> {
+    let player = if c.is_ascii_uppercase() { Player::Black } else { Player::White };
+    let piece = match c.to_ascii_uppercase() {
+        'P' => Piece::Pawn,
+        'L' => Piece::Lance,
+        'N' => Piece::Knight,
+        'S' => Piece::Silver,
+        'G' => Piece::Gold,
+        'B' => Piece::Bishop,
+        'R' => Piece::Rook,
+        'K' => Piece::King,
+        _ => return None,
+    };
+    Some((piece, player))
+}
+
+fn piece_to_sfen(piece: Piece, player: Player) -> String {
+    let letter = match piece {
+        Piece::Pawn => "P",
+        Piece::Lance => "L",
+        Piece::Knight => "N",
+        Piece::Silver => "S",
+        Piece::Gold => "G",
+        Piece::Bishop => "B",
+        Piece::Rook => "R",
+        Piece::King => "K",
+        Piece::PromotedPawn => "+P",
+        Piece::PromotedLance => "+L",
+        Piece::PromotedKnight => "+N",
+        Piece::PromotedSilver => "+S",
+        Piece::PromotedBishop => "+B",
+        Piece::PromotedRook => "+R",
+        Piece::Empty => "",
+    };
+    if player == Player::Black {
+        letter.to_string()
+    } else {
+        letter.to_ascii_lowercase()
+    }
+}
@@ -0,0 +1,75 @@
+// 対局の状態（続行中か、どちらの勝ちで終わったか）
+
+use wasm_bindgen::prelude::*;
+
+use crate::bitboard::square;
+use crate::{Board, Player};
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq)]
+pub enum GameStatus {
+    Ongoing,
+    Checkmate,    // 手番の側が詰んでいる
+    NoLegalMoves, // 王手ではないが、手番の側に指せる手がない（手番の側の負け）
+    KingCaptured, // 玉が取られた
+    Try,          // トライルールで、直前に指した側の玉が相手の玉の初期位置に入った
+}
+
+#[wasm_bindgen]
+impl Board {
+    // 現在の局面の対局の状態
+    #[wasm_bindgen]
+    pub fn game_status(&self) -> GameStatus {
+        if self.captured_king.is_some() {
+            return GameStatus::KingCaptured;
+        }
+        if self.try_rule && self.has_tried(self.current_player.opponent()) {
+            return GameStatus::Try;
+        }
+        if !self.generate_legal_moves().is_empty() {
+            return GameStatus::Ongoing;
+        }
+        if self.is_in_check(self.current_player) {
+            GameStatus::Checkmate
+        } else {
+            GameStatus::NoLegalMoves
+        }
+    }
+
+    // トライルール（相手の玉の初期位置に、相手の駒が利いていない状態で自分の玉を進めたら勝ち）を使うか
+    #[wasm_bindgen]
+    pub fn set_try_rule(&mut self, enabled: bool) {
+        self.try_rule = enabled;
+    }
+
+    #[wasm_bindgen]
+    pub fn is_try_rule(&self) -> bool {
+        self.try_rule
+    }
+
+    // 対局が終わっていれば勝った側（続行中は None）
+    #[wasm_bindgen]
+    pub fn get_winner(&self) -> Option<Player> {
+        self.winner_of(self.game_status())
+    }
+}
+
+impl Board {
+    pub(crate) fn winner_of(&self, status: GameStatus) -> Option<Player> {
+        match status {
+            GameStatus::Ongoing => None,
+            GameStatus::Checkmate | GameStatus::NoLegalMoves | GameStatus::Try => Some(self.current_player.opponent()),
+            GameStatus::KingCaptured => self.captured_king.map(Player::opponent),
+        }
+    }
+
+    // player の玉がトライの目標のマス（先手は５一、後手は５九）にあり、相手の駒が利いていないか
+    fn has_tried(&self, player: Player) -> bool {
+        let target = match player {
+            Player::Black => square(8, 4),
+            Player::White => square(0, 4),
+        };
+        self.king_square(player) == Some(target)
+            && self.attackers_to_square(target, player.opponent(), self.occupied()).is_empty()
+    }
+}
@@ -0,0 +1,298 @@
+// 局面図のSVG出力（印刷用の教材やサーバーでの画像生成に使う）
+//
+// 盤の左に上側の対局者（通常は後手）、右に下側の対局者の持ち駒を縦書きで描く
+
+use std::fmt::Write;
+
+use wasm_bindgen::prelude::*;
+
+use crate::bitboard::position_of;
+use crate::movegen::HAND_PIECES;
+use crate::notation::{kanji_number, piece_char};
+use crate::{Board, Move, Player, Position};
+
+const SQUARE: i32 = 40; // マスの大きさ
+const HAND_WIDTH: i32 = 40;
+const BOARD_LEFT: i32 = HAND_WIDTH + 10;
+const BOARD_TOP: i32 = 30; // 筋の番号を書く分
+const BOARD_SIZE: i32 = SQUARE * 9;
+const WIDTH: i32 = BOARD_LEFT + BOARD_SIZE + 30 + HAND_WIDTH;
+const HEIGHT: i32 = BOARD_TOP + BOARD_SIZE + 20;
+const PIECE_FONT: i32 = 28;
+const HAND_FONT: i32 = 22;
+
+const FILE_LABELS: [char; 9] = ['１', '２', '３', '４', '５', '６', '７', '８', '９'];
+const RANK_LABELS: [char; 9] = ['一', '二', '三', '四', '五', '六', '七', '八', '九'];
+
+// 駒の描き方
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum PieceStyle {
+    #[default]
+    Kanji, // 文字だけ
+    Tile,  // 五角形の駒の上に文字
+}
+
+// SVG出力の設定
+#[wasm_bindgen]
+#[derive(Clone, Default)]
+pub struct SvgOptions {
+    flipped: bool,                      // 後手側から見た盤面にする
+    last_move: Option<Move>,            // 直前の指し手（移動元・移動先のマスを塗る）
+    arrows: Vec<(Position, Position)>, // 矢印（移動元・移動先）
+    piece_style: PieceStyle,
+    font_family: Option<String>, // 省略時は serif
+}
+
+#[wasm_bindgen]
+impl SvgOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> SvgOptions {
+        SvgOptions::default()
+    }
+
+    #[wasm_bindgen]
+    pub fn set_flipped(&mut self, flipped: bool) {
+        self.flipped = flipped;
+    }
+
+    #[wasm_bindgen]
+    pub fn set_last_move(&mut self, mv: &Move) {
+        self.last_move = Some(*mv);
+    }
+
+    #[wasm_bindgen]
+    pub fn set_piece_style(&mut self, style: PieceStyle) {
+        self.piece_style = style;
+    }
+
+    #[wasm_bindgen]
+    pub fn set_font_family(&mut self, font_family: &str) {
+        self.font_family = Some(font_family.to_string());
+    }
+
+    #[wasm_bindgen]
+    pub fn add_arrow(&mut self, from_row: i32, from_col: i32, to_row: i32, to_col: i32) {
+        self.arrows.push((Position::new(from_row, from_col), Position::new(to_row, to_col)));
+    }
+}
+
+impl SvgOptions {
+    // 盤上のマスの、表示上の左上の座標
+    fn square_origin(&self, pos: Position) -> (i32, i32) {
+        let (display_row, display_col) = if self.flipped {
+            (pos.row, 8 - pos.column)
+        } else {
+            (8 - pos.row, pos.column)
+        };
+        (BOARD_LEFT + display_col * SQUARE, BOARD_TOP + display_row * SQUARE)
+    }
+
+    fn square_center(&self, pos: Position) -> (i32, i32) {
+        let (x, y) = self.square_origin(pos);
+        (x + SQUARE / 2, y + SQUARE / 2)
+    }
+}
+
+#[wasm_bindgen]
+impl Board {
+    // 局面図をSVG文字列にする
+    #[wasm_bindgen]
+    pub fn to_svg(&self, options: &SvgOptions) -> String {
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="{}">"#,
+            escape_attribute(options.font_family.as_deref().unwrap_or("serif")),
+            w = WIDTH,
+            h = HEIGHT
+        );
+        svg.push_str(
+            r##"<defs><marker id="arrowhead" viewBox="0 0 10 10" refX="8" refY="5" markerWidth="4" markerHeight="4" orient="auto"><path d="M0,0 L10,5 L0,10 z" fill="#d32f2f"/></marker></defs>"##,
+        );
+        svg.push('\n');
+        let _ = writeln!(svg, r##"<rect width="{}" height="{}" fill="#ffffff"/>"##, WIDTH, HEIGHT);
+        let _ = writeln!(
+            svg,
+            r##"<rect x="{}" y="{}" width="{s}" height="{s}" fill="#f3d9a4"/>"##,
+            BOARD_LEFT,
+            BOARD_TOP,
+            s = BOARD_SIZE
+        );
+
+        self.write_last_move(&mut svg, options);
+        write_grid(&mut svg);
+        write_coordinates(&mut svg, options.flipped);
+
+        for sq in self.occupied() {
+            let (piece, player) = self.pieces[sq];
+            let (cx, cy) = options.square_center(position_of(sq));
+            // 盤の上側に向いている駒（通常は後手の駒）は逆さに描く
+            let rotate = if (player == Player::White) != options.flipped {
+                format!(r#" transform="rotate(180 {} {})""#, cx, cy)
+            } else {
+                String::new()
+            };
+            let color = if self.is_promoted_piece(piece) { "#c62828" } else { "#000000" };
+            let _ = write!(svg, "<g{}>", rotate);
+            if options.piece_style == PieceStyle::Tile {
+                let _ = write!(
+                    svg,
+                    r##"<polygon points="{},{} {},{} {},{} {},{} {},{}" fill="#f8e7c0" stroke="#5d4037" stroke-width="1"/>"##,
+                    cx,
+                    cy - 18,
+                    cx + 12,
+                    cy - 13,
+                    cx + 15,
+                    cy + 17,
+                    cx - 15,
+                    cy + 17,
+                    cx - 12,
+                    cy - 13
+                );
+            }
+            let _ = writeln!(
+                svg,
+                r#"<text class="piece" x="{}" y="{}" font-size="{}" text-anchor="middle" dominant-baseline="central" fill="{}">{}</text></g>"#,
+                cx,
+                cy,
+                PIECE_FONT,
+                color,
+                piece_char(piece)
+            );
+        }
+
+        for &(from, to) in &options.arrows {
+            let (x1, y1) = options.square_center(from);
+            let (x2, y2) = options.square_center(to);
+            let _ = writeln!(
+                svg,
+                r##"<line class="arrow" x1="{}" y1="{}" x2="{}" y2="{}" stroke="#d32f2f" stroke-width="4" stroke-opacity="0.8" marker-end="url(#arrowhead)"/>"##,
+                x1,
+                y1,
+                x2,
+                y2
+            );
+        }
+
+        let (left, right) = if options.flipped {
+            (Player::Black, Player::White)
+        } else {
+            (Player::White, Player::Black)
+        };
+        self.write_hand(&mut svg, left, HAND_WIDTH / 2);
+        self.write_hand(&mut svg, right, WIDTH - HAND_WIDTH / 2);
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+impl Board {
+    fn write_last_move(&self, svg: &mut String, options: &SvgOptions) {
+        let Some(mv) = options.last_move else {
+            return;
+        };
+        let mut squares = vec![(mv.to, "#f6d365")];
+        if !mv.is_drop() {
+            squares.push((mv.from, "#fbe9a7"));
+        }
+        for (pos, color) in squares {
+            let (x, y) = options.square_origin(pos);
+            let _ = writeln!(
+                svg,
+                r#"<rect class="last-move" x="{}" y="{}" width="{s}" height="{s}" fill="{}"/>"#,
+                x,
+                y,
+                color,
+                s = SQUARE
+            );
+        }
+    }
+
+    // 持ち駒を縦書きで描く（例: ☗ 持 駒 飛 角 歩 二）
+    fn write_hand(&self, svg: &mut String, player: Player, x: i32) {
+        let mut text: Vec<char> = vec![if player == Player::Black { '☗' } else { '☖' }, '持', '駒', '　'];
+        let mut empty = true;
+        for &piece in HAND_PIECES.iter().rev() {
+            let count = self.get_captured_piece_count(player, piece);
+            if count > 0 {
+                empty = false;
+                text.push(piece_char(piece));
+                if count > 1 {
+                    text.extend(kanji_number(count as u32).chars());
+                }
+            }
+        }
+        if empty {
+            text.extend("なし".chars());
+        }
+
+        // 盤の高さに収まらないときは字を小さくする
+        let step = (HAND_FONT + 2).min(BOARD_SIZE / text.len() as i32);
+        let font = step - 2;
+        let _ = write!(svg, r#"<g class="hand" font-size="{}" text-anchor="middle">"#, font);
+        for (i, c) in text.iter().enumerate() {
+            let y = BOARD_TOP + font + i as i32 * step;
+            let _ = write!(svg, r#"<text x="{}" y="{}">{}</text>"#, x, y, c);
+        }
+        svg.push_str("</g>\n");
+    }
+}
+
+fn write_grid(svg: &mut String) {
+    for i in 0..=9 {
+        let offset = i * SQUARE;
+        let width = if i == 0 || i == 9 { 2 } else { 1 };
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{x}" y1="{}" x2="{x}" y2="{}" stroke="#000000" stroke-width="{}"/>"##,
+            BOARD_TOP,
+            BOARD_TOP + BOARD_SIZE,
+            width,
+            x = BOARD_LEFT + offset
+        );
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{}" y1="{y}" x2="{}" y2="{y}" stroke="#000000" stroke-width="{}"/>"##,
+            BOARD_LEFT,
+            BOARD_LEFT + BOARD_SIZE,
+            width,
+            y = BOARD_TOP + offset
+        );
+    }
+    // 星（三段目・六段目と三筋・六筋の交点）
+    for x in [3, 6] {
+        for y in [3, 6] {
+            let _ = writeln!(
+                svg,
+                r##"<circle cx="{}" cy="{}" r="3" fill="#000000"/>"##,
+                BOARD_LEFT + x * SQUARE,
+                BOARD_TOP + y * SQUARE
+            );
+        }
+    }
+}
+
+// 盤の上に筋（９〜１）、右に段（一〜九）を書く。反転した盤では逆順になる
+fn write_coordinates(svg: &mut String, flipped: bool) {
+    let _ = write!(svg, r#"<g class="coordinates" font-size="16" text-anchor="middle">"#);
+    for i in 0..9 {
+        let file = if flipped { i } else { 8 - i };
+        let rank = if flipped { 8 - i } else { i };
+        let center = i as i32 * SQUARE + SQUARE / 2;
+        let _ = write!(svg, r#"<text x="{}" y="{}">{}</text>"#, BOARD_LEFT + center, BOARD_TOP - 10, FILE_LABELS[file]);
+        let _ = write!(
+            svg,
+            r#"<text x="{}" y="{}" dominant-baseline="central">{}</text>"#,
+            BOARD_LEFT + BOARD_SIZE + 15,
+            BOARD_TOP + center,
+            RANK_LABELS[rank]
+        );
+    }
+    svg.push_str("</g>\n");
+}
+
+fn escape_attribute(value: &str) -> String {
+    value.replace('&', "&amp;").replace('"', "&quot;").replace('<', "&lt;")
+}
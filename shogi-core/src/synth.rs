@@ -0,0 +1,67 @@
+// ランダムな局面の生成（OCRの学習・評価用データに使う）
+//
+// 駒をでたらめに並べると実戦に出てこない配置ばかりになるため、
+// 平手の初期局面から合法手をランダムに指して局面を作る
+
+use wasm_bindgen::prelude::*;
+
+use crate::bitboard::square_of;
+use crate::zobrist::splitmix64;
+use crate::{Board, Piece};
+
+// 駒を取る手がある場合に、その中から選ぶ確率（百分率）
+const CAPTURE_PERCENT: u64 = 40;
+
+// 乱数（splitmix64）。同じ seed からは同じ列になる（datagen 機能ではデータ生成ツールにも公開する）
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let (state, value) = splitmix64(self.0);
+        self.0 = state;
+        value
+    }
+
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    // 0以上1未満（データ生成ツールだけが使う）
+    #[cfg(feature = "datagen")]
+    pub fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[wasm_bindgen]
+impl Board {
+    // 平手の初期局面から plies 手をランダムに指した局面を作る（詰んだらそこで止める）
+    // 同じ seed からは同じ局面になる
+    #[wasm_bindgen]
+    pub fn random_playout(seed: u64, plies: u32) -> Board {
+        let mut rng = Rng::new(seed);
+        let mut board = Board::new();
+        for _ in 0..plies {
+            let moves = board.generate_legal_moves();
+            if moves.is_empty() {
+                break;
+            }
+            // 取る手を選びやすくして、持ち駒や成り駒のある局面を増やす
+            let captures: Vec<_> = moves
+                .iter()
+                .filter(|mv| !mv.is_drop() && board.pieces[square_of(mv.to)].0 != Piece::Empty)
+                .collect();
+            let mv = if !captures.is_empty() && rng.next_u64() % 100 < CAPTURE_PERCENT {
+                *captures[rng.below(captures.len())]
+            } else {
+                moves[rng.below(moves.len())]
+            };
+            board.do_move(mv);
+        }
+        board
+    }
+}
@@ -0,0 +1,148 @@
+// 対局の終わり方と、棋譜（KIF・CSA・JKF）の終局の書き方
+
+use wasm_bindgen::prelude::*;
+
+use crate::notation::player_name;
+use crate::{Board, GameStatus, Player};
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq)]
+pub enum Termination {
+    Resign,       // 投了
+    Checkmate,    // 詰み
+    Repetition,   // 千日手
+    Impasse,      // 持将棋
+    IllegalWin,   // 反則勝ち（直前に指した側の反則）
+    IllegalLoss,  // 反則負け（手番の側の反則）
+    TimeUp,       // 切れ負け
+    Interrupt,    // 中断
+    EnteringKing, // 入玉勝ち（入玉宣言）
+    Try,          // トライ
+}
+
+// 終局した対局の結果
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct GameResult {
+    termination: Termination,
+    plies: u32,     // 終局までに指した手数
+    player: Player, // 終局したときの手番の側（投了・時間切れ・宣言をした側）
+}
+
+#[wasm_bindgen]
+impl GameResult {
+    #[wasm_bindgen(constructor)]
+    pub fn new(termination: Termination, plies: u32, player: Player) -> GameResult {
+        GameResult { termination, plies, player }
+    }
+
+    #[wasm_bindgen]
+    pub fn get_termination(&self) -> Termination {
+        self.termination
+    }
+
+    #[wasm_bindgen]
+    pub fn get_plies(&self) -> u32 {
+        self.plies
+    }
+
+    #[wasm_bindgen]
+    pub fn get_player(&self) -> Player {
+        self.player
+    }
+
+    // 勝った側（千日手・持将棋・中断は None）
+    #[wasm_bindgen]
+    pub fn get_winner(&self) -> Option<Player> {
+        match self.termination {
+            Termination::Repetition | Termination::Impasse | Termination::Interrupt => None,
+            Termination::IllegalWin | Termination::EnteringKing => Some(self.player),
+            _ => Some(self.player.opponent()),
+        }
+    }
+
+    // KIFの指し手の欄に書く終局の表記（例: "投了"）
+    #[wasm_bindgen]
+    pub fn get_kif_move_name(&self) -> String {
+        match self.termination {
+            Termination::Resign => "投了",
+            Termination::Checkmate => "詰み",
+            Termination::Repetition => "千日手",
+            Termination::Impasse => "持将棋",
+            Termination::IllegalWin => "反則勝ち",
+            Termination::IllegalLoss => "反則負け",
+            Termination::TimeUp => "切れ負け",
+            Termination::Interrupt => "中断",
+            Termination::EnteringKing => "入玉勝ち",
+            Termination::Try => "トライ",
+        }
+        .to_string()
+    }
+
+    // KIFの最後の行（例: "まで64手で後手の勝ち"）
+    #[wasm_bindgen]
+    pub fn get_kif_summary(&self) -> String {
+        let winner = player_name(self.get_winner().unwrap_or(self.player));
+        let loser = player_name(self.player);
+        let result = match self.termination {
+            Termination::Resign | Termination::Checkmate => format!("{}の勝ち", winner),
+            Termination::Repetition => "千日手".to_string(),
+            Termination::Impasse => "持将棋".to_string(),
+            Termination::IllegalWin => format!("{}の反則勝ち", winner),
+            Termination::IllegalLoss => format!("{}の反則負け", loser),
+            Termination::TimeUp => format!("時間切れにより{}の勝ち", winner),
+            Termination::Interrupt => "中断".to_string(),
+            Termination::EnteringKing => format!("入玉宣言により{}の勝ち", winner),
+            Termination::Try => format!("トライにより{}の勝ち", winner),
+        };
+        format!("まで{}手で{}", self.plies, result)
+    }
+
+    // CSAの終局の行（例: "%TORYO"）
+    // 時間切れはCSAサーバーからは "#TIME_UP" で通知されるが、棋譜ファイルには "%TIME_UP" と書く。
+    // トライはCSAに表記がないため、宣言勝ちと同じ "%KACHI" にする（USIの "bestmove win" と同じ扱い）
+    #[wasm_bindgen]
+    pub fn get_csa(&self) -> String {
+        format!("%{}", self.special_name())
+    }
+
+    // JKFの終局の指し手（例: {"special":"TORYO"}）。JKFの "moves" 配列の最後に加える要素のJSON。
+    // 値は英大文字と記号だけなので、serde を使わずに組み立てる（serde 機能なしのビルドでも使えるように）
+    #[wasm_bindgen]
+    pub fn get_jkf(&self) -> String {
+        format!("{{\"special\":\"{}\"}}", self.special_name())
+    }
+}
+
+impl GameResult {
+    // CSA・JKFで共通の終局の名前
+    fn special_name(&self) -> String {
+        let sign = |player: Player| if player == Player::Black { "+" } else { "-" };
+        match self.termination {
+            Termination::Resign => "TORYO".to_string(),
+            Termination::Checkmate => "TSUMI".to_string(),
+            Termination::Repetition => "SENNICHITE".to_string(),
+            Termination::Impasse => "JISHOGI".to_string(),
+            // 反則した側の符号を付ける
+            Termination::IllegalWin => format!("{}ILLEGAL_ACTION", sign(self.player.opponent())),
+            Termination::IllegalLoss => format!("{}ILLEGAL_ACTION", sign(self.player)),
+            Termination::TimeUp => "TIME_UP".to_string(),
+            Termination::Interrupt => "CHUDAN".to_string(),
+            Termination::EnteringKing | Termination::Try => "KACHI".to_string(),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Board {
+    // 盤上で対局が終わっていれば、その終わり方（玉を取られたのは手番の側の反則負けとする）
+    #[wasm_bindgen]
+    pub fn termination(&self) -> Option<Termination> {
+        match self.game_status() {
+            GameStatus::Ongoing => None,
+            GameStatus::Checkmate | GameStatus::NoLegalMoves => Some(Termination::Checkmate),
+            GameStatus::KingCaptured => Some(Termination::IllegalLoss),
+            GameStatus::Try => Some(Termination::Try),
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use wasm_bindgen::prelude::*;
+
+use crate::bitboard::{position_of, Bitboard};
+use crate::movegen::{is_dead_square, HAND_PIECES};
+use crate::notation::{piece_name, player_name, square_name};
+use crate::{Board, Piece, Player};
+
+// 局面の問題の種類
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum IssueKind {
+    KingCount,          // 玉の数が不正
+    TooManyPieces,      // 駒の総数が40枚を超えている
+    PieceCountExceeded, // 駒の種類ごとの枚数を超えている
+    DoublePawn,         // 二歩
+    DeadPiece,          // 行き所のない駒
+    OpponentInCheck,    // 手番でない側に王手がかかっている
+    BothKingsInCheck,   // 両方の玉に王手がかかっている
+}
+
+// validate() が返す問題1件
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct ValidationIssue {
+    pub(crate) kind: IssueKind,
+    pub(crate) is_error: bool, // falseの場合は警告（詰将棋で攻め方の玉がない場合など）
+    pub(crate) row: i32,       // 問題のあるマス（マスに関係しない場合は-1）
+    pub(crate) column: i32,
+    pub(crate) piece: Piece,   // 枚数超過の駒の種類（それ以外はEmpty）
+    message: String,
+}
+
+#[wasm_bindgen]
+impl ValidationIssue {
+    #[wasm_bindgen]
+    pub fn get_kind(&self) -> IssueKind {
+        self.kind
+    }
+
+    #[wasm_bindgen]
+    pub fn is_error(&self) -> bool {
+        self.is_error
+    }
+
+    #[wasm_bindgen]
+    pub fn get_row(&self) -> i32 {
+        self.row
+    }
+
+    #[wasm_bindgen]
+    pub fn get_column(&self) -> i32 {
+        self.column
+    }
+
+    #[wasm_bindgen]
+    pub fn get_piece(&self) -> Piece {
+        self.piece
+    }
+
+    #[wasm_bindgen]
+    pub fn get_message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl ValidationIssue {
+    fn new(kind: IssueKind, message: String) -> ValidationIssue {
+        ValidationIssue { kind, is_error: true, row: -1, column: -1, piece: Piece::Empty, message }
+    }
+
+    fn at(kind: IssueKind, sq: usize, message: String) -> ValidationIssue {
+        let pos = position_of(sq);
+        ValidationIssue { kind, is_error: true, row: pos.row, column: pos.column, piece: Piece::Empty, message }
+    }
+}
+
+// 1組の駒に含まれる枚数（成り駒は元の駒として数える）
+pub(crate) const PIECE_LIMITS: [(Piece, i32); 7] = [
+    (Piece::Pawn, 18),
+    (Piece::Lance, 4),
+    (Piece::Knight, 4),
+    (Piece::Silver, 4),
+    (Piece::Gold, 4),
+    (Piece::Bishop, 2),
+    (Piece::Rook, 2),
+];
+
+const TOTAL_PIECES: i32 = 40;
+
+#[wasm_bindgen]
+impl Board {
+    // 局面の問題点をすべて列挙する（問題がなければ空）
+    #[wasm_bindgen]
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = self.placement_issues();
+        self.validate_checks(&mut issues);
+        issues
+    }
+}
+
+impl Board {
+    // 駒の配置だけで判定できる問題（手番や王手に関係しないもの）
+    pub(crate) fn placement_issues(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        self.validate_kings(&mut issues);
+        self.validate_piece_counts(&mut issues);
+        self.validate_pawns_and_dead_pieces(&mut issues);
+        issues
+    }
+
+    fn validate_kings(&self, issues: &mut Vec<ValidationIssue>) {
+        for player in [Player::Black, Player::White] {
+            let count = self.pieces_of(Piece::King, player).count();
+            if count > 1 {
+                issues.push(ValidationIssue::new(
+                    IssueKind::KingCount,
+                    format!("{}の玉が{}枚あります", player_name(player), count),
+                ));
+            } else if count == 0 {
+                let mut issue = ValidationIssue::new(
+                    IssueKind::KingCount,
+                    format!("{}の玉がありません", player_name(player)),
+                );
+                issue.is_error = false;
+                issues.push(issue);
+            }
+        }
+    }
+
+    fn validate_piece_counts(&self, issues: &mut Vec<ValidationIssue>) {
+        let mut total = self.occupied().count() as i32;
+        for player in [Player::Black, Player::White] {
+            for piece in HAND_PIECES {
+                total += self.get_captured_piece_count(player, piece);
+            }
+        }
+        if total > TOTAL_PIECES {
+            issues.push(ValidationIssue::new(
+                IssueKind::TooManyPieces,
+                format!("駒が{}枚あります（最大{}枚）", total, TOTAL_PIECES),
+            ));
+        }
+
+        for (piece, limit) in PIECE_LIMITS {
+            let mut on_board = self.by_piece[piece as usize];
+            if let Some(promoted) = self.get_promoted_piece(piece) {
+                on_board |= self.by_piece[promoted as usize];
+            }
+            let in_hand: i32 = [Player::Black, Player::White]
+                .iter()
+                .map(|&player| self.get_captured_piece_count(player, piece))
+                .sum();
+            let count = on_board.count() as i32 + in_hand;
+            if count > limit {
+                let mut issue = ValidationIssue::new(
+                    IssueKind::PieceCountExceeded,
+                    format!("{}が{}枚あります（最大{}枚）", piece_name(piece), count, limit),
+                );
+                issue.piece = piece;
+                issues.push(issue);
+            }
+        }
+    }
+
+    fn validate_pawns_and_dead_pieces(&self, issues: &mut Vec<ValidationIssue>) {
+        for player in [Player::Black, Player::White] {
+            let pawns = self.pieces_of(Piece::Pawn, player);
+            for col in 0..9 {
+                let in_file = pawns & Bitboard::file(col);
+                if in_file.count() > 1 {
+                    // 2枚目以降の歩の位置を示す
+                    for sq in in_file.skip(1) {
+                        issues.push(ValidationIssue::at(
+                            IssueKind::DoublePawn,
+                            sq,
+                            format!("{}の{}の歩は二歩です", player_name(player), square_name(sq)),
+                        ));
+                    }
+                }
+            }
+
+            for piece in [Piece::Pawn, Piece::Lance, Piece::Knight] {
+                for sq in self.pieces_of(piece, player) {
+                    if is_dead_square(piece, player, position_of(sq).row) {
+                        issues.push(ValidationIssue::at(
+                            IssueKind::DeadPiece,
+                            sq,
+                            format!("{}の{}の駒は動けません", player_name(player), square_name(sq)),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    fn validate_checks(&self, issues: &mut Vec<ValidationIssue>) {
+        let side = self.current_player;
+        let opponent = side.opponent();
+        if !self.is_in_check(opponent) {
+            return;
+        }
+        if self.is_in_check(side) {
+            issues.push(ValidationIssue::new(
+                IssueKind::BothKingsInCheck,
+                "両方の玉に王手がかかっています".to_string(),
+            ));
+        } else {
+            issues.push(ValidationIssue::new(
+                IssueKind::OpponentInCheck,
+                format!("手番でない{}の玉に王手がかかっています", player_name(opponent)),
+            ));
+        }
+    }
+}
@@ -0,0 +1,60 @@
+// 局面のハッシュ値（Zobrist hashing）に使う乱数表
+
+pub(crate) const fn splitmix64(state: u64) -> (u64, u64) {
+    let state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    (state, z ^ (z >> 31))
+}
+
+// [手番][駒][マス]
+pub(crate) static PIECE_KEYS: [[[u64; 81]; 15]; 2] = {
+    let mut keys = [[[0u64; 81]; 15]; 2];
+    let mut state = 0x5348_4f47_4931_0001; // 任意の初期値
+    let mut player = 0;
+    while player < 2 {
+        let mut piece = 0;
+        while piece < 15 {
+            let mut sq = 0;
+            while sq < 81 {
+                let (next_state, key) = splitmix64(state);
+                state = next_state;
+                keys[player][piece][sq] = key;
+                sq += 1;
+            }
+            piece += 1;
+        }
+        player += 1;
+    }
+    keys
+};
+
+// 持ち駒の枚数として区別する上限（歩の18枚）
+pub(crate) const MAX_HAND_COUNT: usize = 18;
+
+// [手番][持ち駒の種類][枚数]
+pub(crate) static HAND_KEYS: [[[u64; MAX_HAND_COUNT + 1]; 7]; 2] = {
+    let mut keys = [[[0u64; MAX_HAND_COUNT + 1]; 7]; 2];
+    let mut state = 0x5348_4f47_4931_0002;
+    let mut player = 0;
+    while player < 2 {
+        let mut piece = 0;
+        while piece < 7 {
+            // 0枚のときは値を持たない
+            let mut count = 1;
+            while count <= MAX_HAND_COUNT {
+                let (next_state, key) = splitmix64(state);
+                state = next_state;
+                keys[player][piece][count] = key;
+                count += 1;
+            }
+            piece += 1;
+        }
+        player += 1;
+    }
+    keys
+};
+
+// 後手番のときに排他的論理和をとる値
+pub(crate) const SIDE_KEY: u64 = splitmix64(0x5348_4f47_4931_0003).1;
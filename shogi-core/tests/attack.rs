@@ -0,0 +1,48 @@
+use shogi_core::{Board, Player, Position};
+
+fn count(counts: &[u8], player: Player, row: i32, col: i32) -> u8 {
+    counts[player as usize * 81 + (row * 9 + col) as usize]
+}
+
+#[test]
+fn attack_counts_in_initial_position() {
+    let board = Board::new();
+    let counts = board.attack_counts();
+    assert_eq!(counts.len(), 162);
+    // ７六のマスには先手の歩だけが利く
+    assert_eq!(count(&counts, Player::Black, 3, 2), 1);
+    assert_eq!(count(&counts, Player::White, 3, 2), 0);
+    // ５八には先手の金2枚・玉・飛車が利く
+    assert_eq!(count(&counts, Player::Black, 1, 4), 4);
+    // 後手の５二も同じ
+    assert_eq!(count(&counts, Player::White, 7, 4), 4);
+    // 先後の利きの総数は同じ
+    let black: u32 = counts[..81].iter().map(|&c| c as u32).sum();
+    let white: u32 = counts[81..].iter().map(|&c| c as u32).sum();
+    assert_eq!(black, white);
+}
+
+#[test]
+fn attackers_follow_sliding_pieces() {
+    let board = Board::from_sfen("4k4/9/9/9/4p4/9/9/9/1B2R3K b - 1").unwrap();
+    // ５五の歩には飛車が利くが、その先の５一には利かない
+    let attackers = board.attackers_of(Position::new(4, 4), Player::Black);
+    assert_eq!(attackers.len(), 1);
+    assert!(attackers[0] == Position::new(0, 4));
+    assert!(!board.is_square_attacked(Position::new(8, 4), Player::Black));
+    assert!(board.is_square_attacked(Position::new(7, 4), Player::White));
+
+    // ５六には角と飛車の両方が、６七には角だけが利く
+    assert_eq!(board.attackers_of(Position::new(3, 4), Player::Black).len(), 2);
+    assert!(board.attackers_of(Position::new(2, 3), Player::Black) == vec![Position::new(0, 1)]);
+}
+
+#[test]
+fn off_board_positions_have_no_attackers() {
+    // Position::new は盤外の値を (0, 0) にするので、フィールドを直接指定して盤外のマスを作る
+    let board = Board::from_sfen("4k4/9/9/9/9/9/9/9/R3K4 b - 1").unwrap();
+    let off_board = Position { row: 0, column: 9 };
+    assert!(board.attackers_of(off_board, Player::Black).is_empty());
+    assert!(!board.is_square_attacked(off_board, Player::Black));
+    assert!(!board.is_square_attacked(Position { row: -1, column: 0 }, Player::Black));
+}
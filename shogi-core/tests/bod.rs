@@ -0,0 +1,69 @@
+use shogi_core::{Board, Piece, Player};
+
+const INITIAL: &str = "\
+後手の持駒：なし
+  ９ ８ ７ ６ ５ ４ ３ ２ １
++---------------------------+
+|v香v桂v銀v金v玉v金v銀v桂v香|一
+| ・v飛 ・ ・ ・ ・ ・v角 ・|二
+|v歩v歩v歩v歩v歩v歩v歩v歩v歩|三
+| ・ ・ ・ ・ ・ ・ ・ ・ ・|四
+| ・ ・ ・ ・ ・ ・ ・ ・ ・|五
+| ・ ・ ・ ・ ・ ・ ・ ・ ・|六
+| 歩 歩 歩 歩 歩 歩 歩 歩 歩|七
+| ・ 角 ・ ・ ・ ・ ・ 飛 ・|八
+| 香 桂 銀 金 玉 金 銀 桂 香|九
++---------------------------+
+先手の持駒：なし
+先手番";
+
+#[test]
+fn initial_position_diagram() {
+    assert_eq!(Board::new().to_bod(), INITIAL);
+    assert_eq!(Board::from_bod(INITIAL).unwrap().to_sfen(), Board::new().to_sfen());
+}
+
+// 柿木将棋の出力（手数の行や行末の空白を含む）を読み込める
+#[test]
+fn reads_kakinoki_diagram() {
+    let text = "\
+後手の持駒：飛　角　金二　銀　歩十八
+  ９ ８ ７ ６ ５ ４ ３ ２ １
++---------------------------+
+| ・ ・ ・ ・ ・ ・ ・v桂v香|一
+| ・ ・ ・ ・ ・ ・ 全v玉 ・|二
+| ・ ・ ・ ・ ・ ・ ・ ・ ・|三
+| ・ ・ ・ ・ ・ ・ ・ ・ ・|四
+| ・ ・ ・ ・ ・ ・ ・ ・ ・|五
+| ・ ・ ・ ・ ・ ・ ・ ・ ・|六
+| ・ ・ ・ ・ ・ ・ ・ ・ ・|七
+| ・ ・ ・ ・ ・ ・ ・ ・v竜|八
+| ・ ・ ・ ・ ・ ・ ・ ・ 王|九
++---------------------------+
+先手の持駒：金　銀二
+後手番
+手数＝57  ▲３二銀成  まで
+";
+    let board = Board::from_bod(text).unwrap();
+    assert_eq!(board.to_sfen(), "7nl/6+Sk1/9/9/9/9/9/8+r/8K w G2Srb2gs18p 1");
+    assert!(board.get_current_player() == Player::White);
+    assert_eq!(board.get_captured_piece_count(Player::White, Piece::Pawn), 18);
+
+    // 書き出した局面図をもう一度読むと同じ局面になる
+    let bod = board.to_bod();
+    assert!(bod.starts_with("後手の持駒：飛　角　金二　銀　歩十八\n"));
+    assert!(bod.contains("| ・ ・ ・ ・ ・ ・ 全v玉 ・|二"));
+    assert!(bod.ends_with("先手の持駒：金　銀二\n後手番"));
+    assert_eq!(Board::from_bod(&bod).unwrap().to_sfen(), board.to_sfen());
+}
+
+#[test]
+fn rejects_broken_diagram() {
+    assert!(Board::from_bod("").is_err());
+    let missing_square = INITIAL.replace("| ・v飛 ・ ・ ・ ・ ・v角 ・|二", "| ・v飛 ・ ・ ・ ・v角 ・|二");
+    assert!(Board::from_bod(&missing_square).is_err());
+    let unknown_piece = INITIAL.replace("| ・ 角", "| ・ 猫");
+    assert!(Board::from_bod(&unknown_piece).is_err());
+    let king_in_hand = INITIAL.replace("先手の持駒：なし", "先手の持駒：玉");
+    assert!(Board::from_bod(&king_in_hand).is_err());
+}
@@ -0,0 +1,32 @@
+use shogi_core::{Board, Player, Position};
+
+#[test]
+fn recognizes_mino_for_both_sides() {
+    // 先手は美濃囲い（２八玉・３八銀・４九金・５八金・２七歩）、後手は３一金のない居飛車穴熊
+    let board = Board::from_sfen("7nk/7sl/7pp/9/9/9/7P1/4G1SK1/5G3 b - 1").unwrap();
+    let castles = board.castles(Player::Black);
+    assert_eq!(castles[0].get_name(), "美濃囲い");
+    assert!(castles[0].is_complete());
+    assert!(castles[0].get_matched().contains(&Position::new(1, 7)));
+
+    let castles = board.castles(Player::White);
+    assert_eq!(castles[0].get_name(), "居飛車穴熊");
+    assert!(castles[0].get_missing() == vec![Position::new(8, 6)]);
+}
+
+#[test]
+fn partial_formations_are_reported() {
+    // 舟囲いの途中（７八玉・６九金のみ。５八金・６八銀がない）
+    let board = Board::from_sfen("4k4/9/9/9/9/9/9/2K6/3G5 b - 1").unwrap();
+    let castles = board.castles(Player::Black);
+    assert_eq!(castles.len(), 0);
+
+    let board = Board::from_sfen("4k4/9/9/9/9/9/9/2KS5/3G5 b - 1").unwrap();
+    let castles = board.castles(Player::Black);
+    assert_eq!(castles[0].get_name(), "舟囲い");
+    assert!(!castles[0].is_complete());
+    assert!((castles[0].get_completeness() - 0.75).abs() < 1e-9);
+
+    // 初期局面の居玉は囲いではない
+    assert!(Board::new().castles(Player::Black).is_empty());
+}
@@ -0,0 +1,93 @@
+use shogi_core::{Board, Move, Piece, Position};
+
+fn usi_list(moves: &[Move]) -> Vec<String> {
+    let mut list: Vec<String> = moves.iter().map(|mv| mv.to_usi()).collect();
+    list.sort();
+    list
+}
+
+// 合法手のうち、指した後に相手玉が王手になっているもの
+fn checks_by_brute_force(board: &Board) -> Vec<Move> {
+    board
+        .generate_legal_moves()
+        .into_iter()
+        .filter(|&mv| {
+            let mut after = board.clone();
+            after.do_move(mv);
+            after.is_in_check(after.get_current_player())
+        })
+        .collect()
+}
+
+#[test]
+fn checks_match_legal_moves_giving_check() {
+    let positions = [
+        // ５五の銀が動くと香の開き王手
+        "4k4/9/9/9/4S4/9/9/9/4L3K b - 1",
+        // ７七の桂が跳ねると角の開き王手（成る手・成らない手）
+        "8k/9/9/9/9/9/2N6/9/B7K b - 1",
+        // 成って王手になる銀・飛車と、持ち駒を打つ王手
+        "9/4k4/9/3S5/9/9/9/9/K1R6 b GSNLP 1",
+        // 打ち歩詰めになる歩打ちは含まない
+        "7nk/7s1/8G/9/9/9/9/9/K8 b P 1",
+        // 王手されている局面（応手のうち王手になるもの）
+        "4k4/1B7/9/9/4r4/9/9/3S5/4K4 b G 1",
+        "4k4/9/9/9/9/9/9/4r4/4K4 b RG 1",
+        // 王手・成り・打ちが入り組んだ局面
+        "l6nl/5+P1gk/2np1S3/p1p4Pp/3P2Sp1/1PPb2P1P/P5GS1/R8/LN4bKL w RGgsn5p 1",
+        "R8/2K1S1SSk/4B4/9/9/9/9/9/1L1L1L3 b RBGSNLP3g3n17p 1",
+    ];
+    for sfen in positions {
+        let board = Board::from_sfen(sfen).unwrap();
+        assert_eq!(usi_list(&board.generate_checks()), usi_list(&checks_by_brute_force(&board)), "{}", sfen);
+    }
+
+    // 開き王手の手が実際に含まれていること
+    let board = Board::from_sfen("4k4/9/9/9/4S4/9/9/9/4L3K b - 1").unwrap();
+    assert!(usi_list(&board.generate_checks()).contains(&"5e4d".to_string()));
+    let board = Board::from_sfen("7nk/7s1/8G/9/9/9/9/9/K8 b P 1").unwrap();
+    assert!(!usi_list(&board.generate_checks()).contains(&"P*1b".to_string()));
+}
+
+#[test]
+fn evasions_cover_every_way_out_of_check() {
+    // ５五の飛車の王手を、玉の移動・８二角で取る・６八銀の合駒・金打ちの合駒で受ける
+    let board = Board::from_sfen("4k4/1B7/9/9/4r4/9/9/3S5/4K4 b G 1").unwrap();
+    let evasions = board.generate_evasions();
+    assert_eq!(usi_list(&evasions), usi_list(&board.generate_legal_moves()));
+
+    let king = Position::new(0, 4);
+    let rook = Position::new(4, 4);
+    assert!(evasions.iter().any(|mv| !mv.is_drop() && mv.get_from() == king));
+    assert!(evasions.iter().any(|mv| mv.get_piece() == Piece::Bishop && mv.get_to() == rook));
+    assert!(evasions.iter().any(|mv| mv.get_piece() == Piece::Silver && mv.get_to() == Position::new(2, 4)));
+    assert!(evasions.iter().any(|mv| mv.is_drop() && mv.get_piece() == Piece::Gold && mv.get_to() == Position::new(3, 4)));
+    // 王手を受けない手は含まない
+    assert!(!evasions.iter().any(|mv| mv.get_piece() == Piece::Silver && mv.get_to() == Position::new(2, 3)));
+    for &mv in &evasions {
+        let mut after = board.clone();
+        after.do_move(mv);
+        assert!(!after.is_in_check(board.get_current_player()), "{}", mv.to_usi());
+    }
+
+    // 王手されていなければ応手はない
+    assert!(Board::new().generate_evasions().is_empty());
+}
+
+#[test]
+fn evasions_from_double_check_and_pins() {
+    // 飛車と角の両王手は玉を動かすしかない（金で角を取る手は含まない）
+    let board = Board::from_sfen("4k4/9/9/9/4r4/9/9/4K4/5bG2 b - 1").unwrap();
+    let evasions = board.generate_evasions();
+    assert!(!evasions.is_empty());
+    assert!(evasions.iter().all(|mv| mv.get_piece() == Piece::King));
+    assert_eq!(usi_list(&evasions), usi_list(&board.generate_legal_moves()));
+
+    // 角にピンされた金は合駒に使えない（ピンされていなければ使える）
+    let board = Board::from_sfen("4k4/9/9/9/4r4/1b7/9/3G5/4K4 b - 1").unwrap();
+    let evasions = board.generate_evasions();
+    assert!(!evasions.iter().any(|mv| mv.get_piece() == Piece::Gold));
+    assert_eq!(usi_list(&evasions), usi_list(&board.generate_legal_moves()));
+    let board = Board::from_sfen("4k4/9/9/9/4r4/9/9/3G5/4K4 b - 1").unwrap();
+    assert!(board.generate_evasions().iter().any(|mv| mv.get_piece() == Piece::Gold));
+}
@@ -0,0 +1,62 @@
+use shogi_core::{format_kif_time, Clock, Player};
+
+const SECOND: f64 = 1000.0;
+
+#[test]
+fn byoyomi_after_main_time() {
+    // 持ち時間1分、秒読み30秒
+    let mut clock = Clock::new(60.0 * SECOND, 30.0 * SECOND, 1, 0.0);
+    clock.start(0.0, Player::Black);
+    assert_eq!(clock.press(50.0 * SECOND), 50.0 * SECOND);
+    assert!(clock.get_turn() == Player::White);
+    assert_eq!(clock.get_remaining(Player::Black, 50.0 * SECOND), 10.0 * SECOND);
+
+    clock.press(55.0 * SECOND);
+    // 残り10秒から25秒使うと、秒読みに15秒食い込むが間に合う
+    assert_eq!(clock.press(80.0 * SECOND), 25.0 * SECOND);
+    assert_eq!(clock.get_remaining(Player::Black, 80.0 * SECOND), 0.0);
+    assert_eq!(clock.get_byoyomi_remaining(Player::Black, 80.0 * SECOND), 30.0 * SECOND);
+    assert!(clock.get_loser().is_none());
+
+    clock.press(81.0 * SECOND);
+    assert_eq!(clock.get_byoyomi_remaining(Player::Black, 101.0 * SECOND), 10.0 * SECOND);
+    assert!(clock.check_time(110.0 * SECOND).is_none());
+    assert!(clock.check_time(111.0 * SECOND) == Some(Player::Black));
+    assert!(!clock.is_running());
+    assert_eq!(clock.get_consumed(Player::Black), 75.0 * SECOND);
+}
+
+#[test]
+fn multiple_byoyomi_periods() {
+    // 持ち時間なし、秒読み10秒を3回
+    let mut clock = Clock::new(0.0, 10.0 * SECOND, 3, 0.0);
+    clock.start(0.0, Player::Black);
+    clock.press(25.0 * SECOND);
+    assert_eq!(clock.get_periods_left(Player::Black, 25.0 * SECOND), 1);
+    assert!(clock.get_loser().is_none());
+    clock.press(26.0 * SECOND);
+    clock.press(36.0 * SECOND);
+    assert!(clock.get_loser() == Some(Player::Black));
+}
+
+#[test]
+fn fischer_and_sudden_death() {
+    let mut clock = Clock::new(10.0 * SECOND, 0.0, 0, 5.0 * SECOND);
+    clock.start(0.0, Player::White);
+    clock.press(8.0 * SECOND);
+    assert_eq!(clock.get_remaining(Player::White, 8.0 * SECOND), 7.0 * SECOND);
+    assert!(clock.get_turn() == Player::Black);
+
+    // 中断している間は時間が減らない
+    clock.pause(9.0 * SECOND);
+    assert_eq!(clock.get_remaining(Player::Black, 100.0 * SECOND), 9.0 * SECOND);
+    clock.resume(100.0 * SECOND);
+    assert!(clock.check_time(108.0 * SECOND).is_none());
+    assert!(clock.check_time(109.0 * SECOND) == Some(Player::Black));
+}
+
+#[test]
+fn kif_time_format() {
+    assert_eq!(format_kif_time(16_500.0, 83_000.0), "( 0:16/00:01:23)");
+    assert_eq!(format_kif_time(754_000.0, 3_725_000.0), "(12:34/01:02:05)");
+}
@@ -0,0 +1,71 @@
+use shogi_core::{Board, Piece};
+
+fn low_confidence_at(row: usize, col: usize) -> Vec<f64> {
+    let mut confidence = vec![0.9; 81];
+    confidence[row * 9 + col] = 0.3;
+    confidence
+}
+
+#[test]
+fn valid_position_needs_no_correction() {
+    let candidates = Board::new().suggest_corrections(Vec::new(), 5);
+    assert_eq!(candidates.len(), 1);
+    assert!(candidates[0].get_corrections().is_empty());
+}
+
+// 3九の銀を金と読み違えて金が5枚になった局面
+#[test]
+fn fixes_extra_gold_at_least_confident_square() {
+    let board = Board::from_sfen("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGGNL b - 1").unwrap();
+    let candidates = board.suggest_corrections(low_confidence_at(0, 6), 5);
+    assert!(!candidates.is_empty());
+
+    let best = &candidates[0];
+    let corrections = best.get_corrections();
+    assert_eq!(corrections.len(), 1);
+    assert_eq!((corrections[0].get_row(), corrections[0].get_column()), (0, 6));
+    assert!(best.get_board().validate().is_empty());
+    for candidate in &candidates {
+        assert!(candidate.get_board().validate().iter().all(|issue| !issue.is_error()));
+    }
+}
+
+// とを歩と読み違えて二歩になった局面
+#[test]
+fn fixes_double_pawn() {
+    let board = Board::from_sfen("4k4/9/9/9/4P4/9/4P4/9/4K4 b - 1").unwrap();
+    let candidates = board.suggest_corrections(low_confidence_at(4, 4), 3);
+    let corrections = candidates[0].get_corrections();
+    assert_eq!(corrections.len(), 1);
+    assert_eq!((corrections[0].get_row(), corrections[0].get_column()), (4, 4));
+    assert!(candidates.len() <= 3);
+    assert!(candidates
+        .iter()
+        .any(|candidate| candidate.get_corrections()[0].get_new_piece() == Piece::PromotedPawn));
+}
+
+// 先手の玉が3枚ある局面（後手の玉はあるので、余分な2枚は取り除くしかない）
+#[test]
+fn fixes_every_surplus_king() {
+    let board = Board::from_sfen("4k4/9/9/9/9/9/9/9/K3K3K b - 1").unwrap();
+    let candidates = board.suggest_corrections(Vec::new(), 5);
+    assert!(!candidates.is_empty());
+    for candidate in &candidates {
+        assert!(candidate.get_board().validate().iter().all(|issue| !issue.is_error()));
+    }
+    let corrections = candidates[0].get_corrections();
+    assert_eq!(corrections.len(), 2);
+    assert!(corrections.iter().all(|correction| correction.get_new_piece() == Piece::Empty));
+}
+
+// 先手の玉が2枚あり後手の玉がない局面は、片方の先後を直す
+#[test]
+fn fixes_two_kings_of_the_same_side() {
+    let board = Board::from_sfen("9/9/9/9/9/9/9/9/K7K b - 1").unwrap();
+    let candidates = board.suggest_corrections(Vec::new(), 5);
+    let corrections = candidates[0].get_corrections();
+    assert_eq!(corrections.len(), 1);
+    assert!(corrections[0].get_new_piece() == Piece::King);
+    assert!(corrections[0].get_new_player() != corrections[0].get_old_player());
+    assert!(candidates[0].get_board().validate().iter().all(|issue| !issue.is_error()));
+}
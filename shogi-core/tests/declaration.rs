@@ -0,0 +1,56 @@
+use shogi_core::{Board, Clock, DeclarationResult, DeclarationRule, Player};
+
+// 先手玉が５二に入玉し、大駒2枚と小駒8枚が敵陣にある
+const ENTERED: &str = "9/1R2K2B1/LNSG1GSNL/9/9/9/9/9/4k4 b";
+
+#[test]
+fn counts_declaration_points() {
+    assert_eq!(Board::new().declaration_points(Player::Black), 0);
+    let board = Board::from_sfen(&format!("{} 10P 1", ENTERED)).unwrap();
+    assert_eq!(board.declaration_points(Player::Black), 28);
+    assert_eq!(board.declaration_points(Player::White), 0);
+}
+
+#[test]
+fn declaration_under_27_point_rule() {
+    let board = Board::from_sfen(&format!("{} 10P 1", ENTERED)).unwrap();
+    assert!(board.declare_win(DeclarationRule::Points27) == DeclarationResult::Win);
+    // 先手は28点必要
+    let board = Board::from_sfen(&format!("{} 9P 1", ENTERED)).unwrap();
+    assert!(board.declare_win(DeclarationRule::Points27) == DeclarationResult::Invalid);
+
+    // 王手されている・敵陣の駒が9枚・玉が敵陣にいない場合は宣言できない
+    let board = Board::from_sfen("4l4/1R2K2B1/LNSG1GSNL/9/9/9/9/9/4k4 b 10P 1").unwrap();
+    assert!(board.declare_win(DeclarationRule::Points27) == DeclarationResult::Invalid);
+    let board = Board::from_sfen("9/1R2K2B1/1NSG1GSNL/9/9/9/9/9/4k4 b 11P 1").unwrap();
+    assert!(board.declare_win(DeclarationRule::Points27) == DeclarationResult::Invalid);
+    let board = Board::from_sfen("9/1R5B1/LNSG1GSNL/4K4/9/9/9/9/4k4 b 10P 1").unwrap();
+    assert!(board.declare_win(DeclarationRule::Points27) == DeclarationResult::Invalid);
+}
+
+#[test]
+fn declaration_under_24_point_rule() {
+    let board = Board::from_sfen(&format!("{} RB3P 1", ENTERED)).unwrap();
+    assert_eq!(board.declaration_points(Player::Black), 31);
+    assert!(board.declare_win(DeclarationRule::Points24) == DeclarationResult::Win);
+    let board = Board::from_sfen(&format!("{} 10P 1", ENTERED)).unwrap();
+    assert!(board.declare_win(DeclarationRule::Points24) == DeclarationResult::Draw);
+    let board = Board::from_sfen(&format!("{} 5P 1", ENTERED)).unwrap();
+    assert!(board.declare_win(DeclarationRule::Points24) == DeclarationResult::Invalid);
+}
+
+#[test]
+fn declaration_requires_time_remaining() {
+    let board = Board::from_sfen(&format!("{} 10P 1", ENTERED)).unwrap();
+    // 持ち時間1分・秒読みなし
+    let mut clock = Clock::new(60_000.0, 0.0, 0, 0.0);
+    clock.start(0.0, Player::Black);
+    assert!(board.declare_win_on_clock(DeclarationRule::Points27, &clock, 30_000.0) == DeclarationResult::Win);
+    assert!(!clock.is_time_up(Player::Black, 30_000.0));
+
+    // 持ち時間を使い切ってからの宣言は認めない
+    assert!(clock.is_time_up(Player::Black, 60_000.0));
+    assert!(board.declare_win_on_clock(DeclarationRule::Points27, &clock, 60_000.0) == DeclarationResult::Invalid);
+    assert!(clock.check_time(61_000.0) == Some(Player::Black));
+    assert!(board.declare_win_on_clock(DeclarationRule::Points27, &clock, 61_000.0) == DeclarationResult::Invalid);
+}
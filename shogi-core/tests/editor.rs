@@ -0,0 +1,68 @@
+use shogi_core::{Board, Handicap, Piece, Player};
+
+fn total(board: &Board) -> i32 {
+    board.remaining_in_box().iter().sum()
+}
+
+#[test]
+fn piece_box_holds_the_rest_of_the_set() {
+    assert_eq!(Board::new().remaining_in_box(), vec![0; 8]);
+    // 二枚落ちでは飛車と角が駒箱に入っている
+    let board = Board::from_handicap(Handicap::TwoPieces);
+    assert_eq!(board.remaining_in_box(), vec![0, 0, 0, 0, 0, 1, 1, 0]);
+}
+
+// 編集操作では駒が増えたり消えたりしない
+#[test]
+fn edits_conserve_pieces() {
+    let mut board = Board::new();
+    board.clear_board();
+    assert_eq!(total(&board), 40);
+
+    assert!(board.edit_place_from_box(8, 4, Piece::King, Player::White));
+    assert!(board.edit_place_from_box(6, 4, Piece::PromotedPawn, Player::Black));
+    assert!(!board.edit_place_from_box(6, 4, Piece::Gold, Player::Black));
+    assert!(board.edit_flip_side(6, 4));
+    assert!(board.edit_flip_promotion(6, 4));
+    assert!(board.edit_board_to_hand(6, 4, Player::Black));
+    assert_eq!(board.get_captured_piece_count(Player::Black, Piece::Pawn), 1);
+    assert!(board.edit_hand_to_hand(Player::Black, Player::White, Piece::Pawn));
+    assert!(board.edit_hand_to_board(Player::White, Piece::Pawn, 5, 0));
+    assert!(board.edit_place_from_box(5, 1, Piece::Rook, Player::Black));
+    assert!(board.edit_move_on_board(5, 1, 5, 0));
+    assert_eq!(board.get_captured_piece_count(Player::Black, Piece::Pawn), 1);
+    assert_eq!(total(&board), 37);
+
+    // 角は2枚しかない
+    assert!(board.edit_box_to_hand(Piece::Bishop, Player::Black));
+    assert!(board.edit_box_to_hand(Piece::PromotedBishop, Player::Black));
+    assert!(!board.edit_box_to_hand(Piece::Bishop, Player::White));
+    assert!(board.edit_hand_to_box(Player::Black, Piece::Bishop));
+}
+
+#[test]
+fn cycle_piece_goes_through_promotion_and_side() {
+    let mut board = Board::new();
+    board.clear_board();
+    board.edit_place_from_box(4, 4, Piece::Silver, Player::Black);
+    let mut seen = Vec::new();
+    for _ in 0..4 {
+        assert!(board.edit_cycle_piece(4, 4));
+        seen.push(board.to_sfen().split('/').nth(4).unwrap().to_string());
+    }
+    assert_eq!(seen, ["4+S4", "4s4", "4+s4", "4S4"]);
+}
+
+// 詰将棋の「残り駒全部」
+#[test]
+fn send_box_to_defender_hand() {
+    let mut board = Board::new();
+    board.clear_board();
+    board.clear_captured_pieces(Player::Black);
+    board.clear_captured_pieces(Player::White);
+    board.edit_place_from_box(8, 4, Piece::King, Player::White);
+    board.edit_place_from_box(6, 4, Piece::Gold, Player::Black);
+    board.edit_send_box_to_hand(Player::White);
+    assert_eq!(board.to_sfen(), "4k4/9/4G4/9/9/9/9/9/9 b 2r2b3g4s4n4l18p 1");
+    assert_eq!(board.remaining_in_box(), vec![0, 0, 0, 0, 0, 0, 0, 1]);
+}
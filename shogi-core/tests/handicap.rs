@@ -0,0 +1,73 @@
+use shogi_core::{Board, Handicap, Piece, Player};
+
+// 駒落ちごとの落とす駒のマス（筋、段）
+const PRESETS: [(Handicap, &[(i32, i32)]); 11] = [
+    (Handicap::Even, &[]),
+    (Handicap::Lance, &[(1, 1)]),
+    (Handicap::RightLance, &[(9, 1)]),
+    (Handicap::Bishop, &[(2, 2)]),
+    (Handicap::Rook, &[(8, 2)]),
+    (Handicap::RookLance, &[(8, 2), (1, 1)]),
+    (Handicap::TwoPieces, &[(8, 2), (2, 2)]),
+    (Handicap::FourPieces, &[(8, 2), (2, 2), (1, 1), (9, 1)]),
+    (Handicap::SixPieces, &[(8, 2), (2, 2), (1, 1), (9, 1), (2, 1), (8, 1)]),
+    (Handicap::EightPieces, &[(8, 2), (2, 2), (1, 1), (9, 1), (2, 1), (8, 1), (3, 1), (7, 1)]),
+    (Handicap::TenPieces, &[(8, 2), (2, 2), (1, 1), (9, 1), (2, 1), (8, 1), (3, 1), (7, 1), (4, 1), (6, 1)]),
+];
+
+#[test]
+fn presets_remove_the_right_pieces() {
+    let even = Board::new();
+    for (kind, removed) in PRESETS {
+        let board = Board::from_handicap(kind);
+        for row in 0..9 {
+            for col in 0..9 {
+                let is_removed = removed.contains(&(9 - col, 9 - row));
+                let piece = board.get_piece_by_coords(row, col);
+                let original = even.get_piece_by_coords(row, col);
+                if is_removed {
+                    assert!(original.player == Player::White && original.piece != Piece::Empty);
+                    assert!(piece.piece == Piece::Empty, "{}", kind.kif_name());
+                } else {
+                    assert!(piece.piece == original.piece && piece.player == original.player, "{}", kind.kif_name());
+                }
+            }
+        }
+        // 駒落ちは上手（後手）から指す
+        let first = if kind == Handicap::Even { Player::Black } else { Player::White };
+        assert!(board.get_current_player() == first);
+        assert!(kind.first_player() == first);
+    }
+}
+
+#[test]
+fn presets_round_trip_through_kif_and_csa() {
+    for (kind, _) in PRESETS {
+        assert!(Handicap::from_kif_name(kind.kif_name()) == Some(kind));
+
+        let header = kind.csa_header();
+        let mut lines = header.lines();
+        let pi = lines.next().unwrap();
+        let turn = lines.next().unwrap();
+        assert!(Handicap::from_csa_pi(pi) == Some(kind), "{}", pi);
+        assert_eq!(turn, if kind == Handicap::Even { "+" } else { "-" });
+
+        let board = Board::from_handicap(kind);
+        let from_kif = Board::from_handicap(Handicap::from_kif_name(kind.kif_name()).unwrap());
+        let from_csa = Board::from_handicap(Handicap::from_csa_pi(pi).unwrap());
+        assert_eq!(from_kif.to_sfen(), board.to_sfen());
+        assert_eq!(from_csa.to_sfen(), board.to_sfen());
+    }
+}
+
+#[test]
+fn malformed_csa_pi_lines_are_rejected() {
+    assert!(Handicap::from_csa_pi("PI82HI22KA") == Some(Handicap::TwoPieces));
+    // 全角の数字が混ざっていても落ちない
+    assert!(Handicap::from_csa_pi("PI８２HI").is_none());
+    assert!(Handicap::from_csa_pi("PI82HI２２KA").is_none());
+    assert!(Handicap::from_csa_pi("P1-KY-KE").is_none());
+    // 4文字に満たない余りがある
+    assert!(Handicap::from_csa_pi("PI82HI2").is_none());
+    assert!(Handicap::from_kif_name("三枚落ち").is_none());
+}
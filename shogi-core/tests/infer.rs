@@ -0,0 +1,52 @@
+use shogi_core::{infer_moves, Board, Piece, Player, Position};
+
+fn usi(moves: &[shogi_core::Move]) -> Vec<String> {
+    moves.iter().map(|mv| mv.to_usi()).collect()
+}
+
+#[test]
+fn infers_two_plies() {
+    let before = Board::new();
+    let after = Board::from_sfen("lnsgkgsnl/1r5b1/pppppp1pp/6p2/9/2P6/PP1PPPPPP/1B5R1/LNSGKGSNL b - 1").unwrap();
+    let results = infer_moves(&before, &after, 2);
+    assert_eq!(results[0].get_mismatches(), 0);
+    assert_eq!(usi(&results[0].get_moves()), ["7g7f", "3c3d"]);
+}
+
+// 読み取りで1マス誤っていても手順を推定できる
+#[test]
+fn tolerates_one_recognition_error() {
+    let before = Board::from_sfen("lnsgkgsnl/1r5b1/pppppp1pp/6p2/9/2P6/PP1PPPPPP/1B5R1/LNSGKGSNL b - 1").unwrap();
+    let mut after = Board::from_sfen("lnsgkgsnl/1r5+B1/pppppp1pp/6p2/9/2P6/PP1PPPPPP/7R1/LNSGKGSNL w B 1").unwrap();
+    // 2二の角を取って成った馬を、と金と読み違えた
+    after.set_piece(Position::new(7, 7), Piece::PromotedPawn, Player::Black);
+    let results = infer_moves(&before, &after, 1);
+    assert!(results.iter().all(|result| result.get_mismatches() == 1));
+    // 成・不成のどちらを読み違えたかは区別できない
+    let candidates: Vec<Vec<String>> = results.iter().map(|result| usi(&result.get_moves())).collect();
+    assert_eq!(candidates, [["8h2b+"], ["8h2b"]]);
+}
+
+// before は正しい局面として扱う。動かない駒の読み違いは1か所の違いとして許すが、
+// 動かした駒や手番の読み違いは許さない
+#[test]
+fn before_errors_are_tolerated_only_off_the_moved_squares() {
+    let after = Board::from_sfen("lnsgkgsnl/1r5b1/ppppppppp/9/9/2P6/PP1PPPPPP/1B5R1/LNSGKGSNL w - 1").unwrap();
+
+    // ９七の歩を香と読み違えた
+    let mut before = Board::new();
+    before.set_piece(Position::new(2, 0), Piece::Lance, Player::Black);
+    let results = infer_moves(&before, &after, 1);
+    assert_eq!(results[0].get_mismatches(), 1);
+    assert_eq!(usi(&results[0].get_moves()), ["7g7f"]);
+
+    // 動かした７七の歩を読み落とした
+    let mut before = Board::new();
+    before.clear_square(Position::new(2, 2));
+    assert!(infer_moves(&before, &after, 1).is_empty());
+
+    // 手番を後手と読み違えた
+    let mut before = Board::new();
+    before.set_current_player(Player::White);
+    assert!(infer_moves(&before, &after, 1).is_empty());
+}
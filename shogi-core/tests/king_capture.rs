@@ -0,0 +1,27 @@
+use shogi_core::{Board, Piece, Player, Position};
+
+// 玉を取っても持ち駒にはならず、以降は指せない
+#[test]
+fn capturing_king_ends_game() {
+    let mut board = Board::from_sfen("4k4/9/9/9/9/9/9/9/4R3K b - 1").unwrap();
+    assert!(board.make_move(Position::new(0, 4), Position::new(8, 4)));
+    assert!(board.is_king_captured());
+    assert_eq!(board.get_captured_piece_count(Player::Black, Piece::King), 0);
+    assert!(!board.make_move(Position::new(0, 8), Position::new(1, 8)));
+}
+
+#[test]
+fn king_cannot_enter_hand() {
+    let mut board = Board::new();
+    assert!(!board.set_captured_piece_count(Player::Black, Piece::King, 1));
+    board.add_captured_piece(Player::Black, Piece::King);
+    assert_eq!(board.get_captured_piece_count(Player::Black, Piece::King), 0);
+    assert!(Board::from_sfen("4k4/9/9/9/9/9/9/9/4K4 b K 1").is_err());
+
+    // 盤から外した玉は駒箱に入り、持ち駒にはできない
+    assert!(!board.edit_board_to_hand(0, 4, Player::White));
+    assert!(board.edit_board_to_box(0, 4));
+    assert_eq!(board.get_piece_box_count(Piece::King), 1);
+    assert!(board.edit_place_from_box(4, 4, Piece::King, Player::Black));
+    assert_eq!(board.get_piece_box_count(Piece::King), 0);
+}
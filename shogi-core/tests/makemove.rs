@@ -0,0 +1,66 @@
+use shogi_core::{Board, Move, Piece, Player, Position};
+
+fn find_move(board: &Board, from: Position, to: Position, promote: bool) -> Move {
+    board
+        .generate_legal_moves()
+        .into_iter()
+        .find(|mv| !mv.is_drop() && mv.get_from() == from && mv.get_to() == to && mv.is_promotion() == promote)
+        .unwrap()
+}
+
+// do_move の差分更新のハッシュ値が、同じ局面を最初から作った場合と一致し、undo_move で元に戻ること
+fn assert_do_undo(board: &mut Board, mv: Move) {
+    let sfen = board.to_sfen();
+    let hash = board.get_hash();
+    let player = board.get_current_player();
+
+    let undo = board.do_move(mv);
+    assert!(board.get_current_player() != player);
+    assert_eq!(board.get_hash(), Board::from_sfen(&board.to_sfen()).unwrap().get_hash(), "{}", mv.to_usi());
+
+    board.undo_move(mv, undo);
+    assert_eq!(board.to_sfen(), sfen);
+    assert_eq!(board.get_hash(), hash);
+    assert!(board.get_current_player() == player);
+}
+
+#[test]
+fn capturing_promoted_piece_demotes_it_into_hand() {
+    // ５八の金で５七の龍を取る
+    let mut board = Board::from_sfen("4k4/9/9/9/9/9/4+r4/4G4/4K4 b - 1").unwrap();
+    let mv = find_move(&board, Position::new(1, 4), Position::new(2, 4), false);
+
+    let mut after = board.clone();
+    let undo = after.do_move(mv);
+    assert!(undo.get_captured_piece() == Piece::PromotedRook);
+    assert_eq!(after.get_captured_piece_count(Player::Black, Piece::Rook), 1);
+
+    assert_do_undo(&mut board, mv);
+    assert_eq!(board.get_captured_piece_count(Player::Black, Piece::Rook), 0);
+    assert!(board.get_piece_by_coords(2, 4).piece == Piece::PromotedRook);
+}
+
+#[test]
+fn drops_and_promotions_are_undone() {
+    let mut board = Board::from_sfen("4k4/9/9/2S6/9/9/9/9/4K4 b G2P 1").unwrap();
+    let drop = Move::new_drop(Piece::Gold, Position::new(4, 4));
+    assert!(board.generate_legal_moves().contains(&drop));
+    assert_do_undo(&mut board, drop);
+    assert_eq!(board.get_captured_piece_count(Player::Black, Piece::Gold), 1);
+
+    // ７四の銀が６三に成る
+    let promotion = find_move(&board, Position::new(5, 2), Position::new(6, 3), true);
+    assert_do_undo(&mut board, promotion);
+    assert!(board.get_piece_by_coords(5, 2).piece == Piece::Silver);
+
+    // 続けて指した手も順に戻せる
+    let sfen = board.to_sfen();
+    let hash = board.get_hash();
+    let first = board.do_move(drop);
+    let reply = board.generate_legal_moves()[0];
+    let second = board.do_move(reply);
+    board.undo_move(reply, second);
+    board.undo_move(drop, first);
+    assert_eq!(board.to_sfen(), sfen);
+    assert_eq!(board.get_hash(), hash);
+}
@@ -0,0 +1,83 @@
+use shogi_core::{Board, Move, Piece, Player, Position};
+
+// 駒を置いていない盤面（手番は先手）
+fn empty_board() -> Board {
+    let mut board = Board::new();
+    board.clear_board();
+    board.set_current_player(Player::Black);
+    board
+}
+
+fn play(board: &Board, mv: Move) -> Board {
+    let mut after = board.clone();
+    let to = mv.get_to();
+    let played = if mv.is_drop() {
+        after.drop_piece(mv.get_piece(), to.get_row(), to.get_column())
+    } else {
+        after.make_move_with_promotion(mv.get_from(), to, mv.is_promotion())
+    };
+    assert!(played);
+    after
+}
+
+#[test]
+fn evasions_cover_every_way_out_of_check() {
+    // ５五の飛車の王手を、玉の移動・８二角で取る・６八銀の合駒・金打ちの合駒で受ける
+    let mut board = empty_board();
+    board.set_piece_by_coords(0, 4, Piece::King, Player::Black);
+    board.set_piece_by_coords(8, 4, Piece::King, Player::White);
+    board.set_piece_by_coords(4, 4, Piece::Rook, Player::White);
+    board.set_piece_by_coords(7, 1, Piece::Bishop, Player::Black);
+    board.set_piece_by_coords(1, 3, Piece::Silver, Player::Black);
+    board.set_captured_piece_count(Player::Black, Piece::Gold, 1);
+    assert!(board.is_in_check(Player::Black));
+
+    let evasions = board.generate_evasions();
+    let king = Position::new(0, 4);
+    let rook = Position::new(4, 4);
+    assert!(evasions.iter().any(|mv| !mv.is_drop() && mv.get_from() == king));
+    assert!(evasions.iter().any(|mv| mv.get_piece() == Piece::Bishop && mv.get_to() == rook));
+    assert!(evasions.iter().any(|mv| mv.get_piece() == Piece::Silver && mv.get_to() == Position::new(2, 4)));
+    assert!(evasions.iter().any(|mv| mv.is_drop() && mv.get_piece() == Piece::Gold && mv.get_to() == Position::new(3, 4)));
+    // 王手を受けない手は含まない
+    assert!(!evasions.iter().any(|mv| mv.get_piece() == Piece::Silver && mv.get_to() == Position::new(2, 3)));
+    for &mv in &evasions {
+        assert!(!play(&board, mv).is_in_check(Player::Black));
+    }
+
+    // 王手されていなければ応手はない
+    assert!(Board::new().generate_evasions().is_empty());
+}
+
+#[test]
+fn double_check_evasions_are_king_moves() {
+    // 飛車と角の両王手は、金で角を取っても受からない
+    let mut board = empty_board();
+    board.set_piece_by_coords(1, 4, Piece::King, Player::Black);
+    board.set_piece_by_coords(8, 4, Piece::King, Player::White);
+    board.set_piece_by_coords(4, 4, Piece::Rook, Player::White);
+    board.set_piece_by_coords(0, 5, Piece::Bishop, Player::White);
+    board.set_piece_by_coords(0, 6, Piece::Gold, Player::Black);
+
+    let evasions = board.generate_evasions();
+    assert!(!evasions.is_empty());
+    assert!(evasions.iter().all(|mv| mv.get_piece() == Piece::King));
+}
+
+#[test]
+fn checks_give_check() {
+    // ５五の銀が動くと香の開き王手になる
+    let mut board = empty_board();
+    board.set_piece_by_coords(0, 8, Piece::King, Player::Black);
+    board.set_piece_by_coords(8, 4, Piece::King, Player::White);
+    board.set_piece_by_coords(4, 4, Piece::Silver, Player::Black);
+    board.set_piece_by_coords(0, 4, Piece::Lance, Player::Black);
+    board.set_captured_piece_count(Player::Black, Piece::Gold, 1);
+
+    let checks = board.generate_checks();
+    assert!(checks.iter().any(|mv| mv.get_piece() == Piece::Silver && mv.get_to() == Position::new(5, 5)));
+    assert!(checks.iter().any(|mv| mv.is_drop() && mv.get_to() == Position::new(7, 4)));
+    for &mv in &checks {
+        assert!(play(&board, mv).is_in_check(Player::White));
+    }
+}
@@ -0,0 +1,74 @@
+use shogi_core::{Board, Position};
+
+fn usi(board: &Board, text: &str, previous_to: Option<Position>) -> String {
+    let result = board.parse_move_text(text, previous_to);
+    assert!(result.is_unique(), "{}: {:?} {:?}", text, result.get_error(), result.get_candidate_names());
+    result.get_move().unwrap().to_usi()
+}
+
+fn play(board: &mut Board, usi: &str) {
+    let mv = board.move_from_usi(usi).unwrap();
+    assert!(board.make_move_with_promotion(mv.get_from(), mv.get_to(), mv.is_promotion()));
+}
+
+#[test]
+fn accepts_common_spellings() {
+    let board = Board::new();
+    for text in ["76歩", "７六歩", "7六歩", "▲7六歩", "☗７六歩", "76歩(77)", " 7 6 歩 "] {
+        assert_eq!(usi(&board, text, None), "7g7f");
+    }
+    assert!(board.parse_move_text("△34歩", None).get_error() == Some("手番が違います".to_string()));
+    assert!(board.parse_move_text("75歩", None).get_error().is_some());
+    assert!(board.parse_move_text("76猫", None).get_error().is_some());
+    assert!(board.parse_move_text("同歩", None).get_error().is_some());
+}
+
+#[test]
+fn promotion_same_square_and_drop() {
+    let mut board = Board::new();
+    play(&mut board, "7g7f");
+    play(&mut board, "3c3d");
+
+    // 成・不成を書かなければ両方が候補になる
+    let result = board.parse_move_text("22角", None);
+    assert!(!result.is_unique());
+    assert_eq!(result.get_candidate_names().len(), 2);
+    assert!(result.get_candidate_names().contains(&"２二角成(88)".to_string()));
+    assert_eq!(usi(&board, "▲2二角成", None), "8h2b+");
+    assert_eq!(usi(&board, "22角不成", None), "8h2b");
+    play(&mut board, "8h2b+");
+
+    let previous = Some(Position::new(7, 7));
+    assert_eq!(usi(&board, "同銀", previous), "3a2b");
+    assert_eq!(usi(&board, "△同　銀", previous), "3a2b");
+    play(&mut board, "3a2b");
+
+    assert_eq!(usi(&board, "5五角打", None), "B*5e");
+    assert_eq!(usi(&board, "55角", None), "B*5e");
+}
+
+#[test]
+fn relative_position_words() {
+    let board = Board::new();
+    assert!(!board.parse_move_text("58金", None).is_unique());
+    assert_eq!(usi(&board, "58金右", None), "4i5h");
+    assert_eq!(usi(&board, "58金左", None), "6i5h");
+
+    // 後手から見た右は９筋の側
+    let mut board = Board::new();
+    play(&mut board, "7g7f");
+    assert_eq!(usi(&board, "52金右", None), "6a5b");
+    assert_eq!(usi(&board, "52金左", None), "4a5b");
+
+    let board = Board::from_sfen("4k4/9/9/9/9/9/9/3G5/4KG3 b - 1").unwrap();
+    assert_eq!(usi(&board, "58金寄", None), "6h5h");
+    assert_eq!(usi(&board, "58金上", None), "4i5h");
+    assert!(board.parse_move_text("58金引", None).get_error().is_some());
+
+    let board = Board::from_sfen("4k4/9/9/9/9/9/4G4/3G5/4K4 b - 1").unwrap();
+    assert_eq!(usi(&board, "58金引", None), "5g5h");
+
+    let board = Board::from_sfen("4k4/9/9/9/9/9/9/3GG4/4K4 b - 1").unwrap();
+    assert_eq!(usi(&board, "57金直", None), "5h5g");
+    assert_eq!(usi(&board, "57金左", None), "6h5g");
+}
@@ -0,0 +1,33 @@
+use shogi_core::{Board, OcrEvaluation, Piece, Player};
+
+#[test]
+fn counts_each_kind_of_error() {
+    let truth = Board::from_sfen("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b 2P 1").unwrap();
+    // 種類・先後・成り・見落とし・余分を1つずつ、持ち駒1枚と手番を間違える
+    let recognized = Board::from_sfen("lnsgkgsnl/1r5b1/ppppppppp/4p4/9/9/PPPPPPPP+P/1B5R1/LNSSKgSN1 w P 1").unwrap();
+
+    let mut evaluation = OcrEvaluation::new();
+    evaluation.add(&truth, &truth);
+    evaluation.add(&recognized, &truth);
+    evaluation.add_failure();
+
+    assert_eq!(evaluation.get_positions(), 3);
+    assert_eq!(evaluation.get_exact_matches(), 1);
+    assert!((evaluation.exact_match_rate() - 1.0 / 3.0).abs() < 1e-9);
+    assert_eq!(evaluation.get_square_errors(), 5);
+    assert_eq!(evaluation.get_type_errors(), 1);
+    assert_eq!(evaluation.get_side_errors(), 1);
+    assert_eq!(evaluation.get_promotion_errors(), 1);
+    assert_eq!(evaluation.get_missing_pieces(), 1);
+    assert_eq!(evaluation.get_extra_pieces(), 1);
+    assert_eq!(evaluation.get_hand_errors(), 1);
+    assert_eq!(evaluation.get_hand_error_positions(), 1);
+    assert_eq!(evaluation.get_turn_errors(), 1);
+    assert!((evaluation.square_accuracy() - 157.0 / 162.0).abs() < 1e-9);
+
+    assert_eq!(evaluation.get_confusion(Piece::Gold, Player::Black, Piece::Silver, Player::Black), 1);
+    assert_eq!(evaluation.get_confusion(Piece::Pawn, Player::Black, Piece::Pawn, Player::Black), 17);
+    let report = evaluation.report(10);
+    assert!(report.contains("完全一致: 1 (33.3%)"));
+    assert!(report.contains("▲金 → ▲銀: 1"));
+}
@@ -0,0 +1,108 @@
+use shogi_core::{Board, GameStatus, Piece, Player, Position};
+
+#[test]
+fn reports_capture_promotion_and_notation() {
+    let mut board = Board::new();
+    let outcome = board.make_move_with_outcome(Position::new(2, 2), Position::new(3, 2), false).unwrap();
+    assert!(outcome.get_player() == Player::Black);
+    assert!(!outcome.is_capture() && !outcome.is_check());
+    assert!(outcome.get_status() == GameStatus::Ongoing);
+    assert_eq!(outcome.get_usi(), "7g7f");
+    assert_eq!(outcome.get_kif(None), "７六歩(77)");
+
+    board.make_move(Position::new(6, 6), Position::new(5, 6));
+    let outcome = board.make_move_with_outcome(Position::new(1, 1), Position::new(7, 7), true).unwrap();
+    assert!(outcome.is_promotion());
+    assert!(outcome.get_piece() == Piece::Bishop);
+    assert!(outcome.get_placed_piece() == Piece::PromotedBishop);
+    assert!(outcome.get_captured_piece() == Piece::Bishop);
+    assert!(outcome.get_hand_piece() == Piece::Bishop);
+    assert_eq!(outcome.get_usi(), "8h2b+");
+
+    // 成り駒を取ると元の駒が持ち駒になる
+    let outcome = board.make_move_with_outcome(Position::new(8, 6), Position::new(7, 7), false).unwrap();
+    assert!(outcome.get_captured_piece() == Piece::PromotedBishop);
+    assert!(outcome.get_hand_piece() == Piece::Bishop);
+    assert_eq!(outcome.get_kif(Some(Position::new(7, 7))), "同　銀(31)");
+
+    let outcome = board.drop_piece_with_outcome(Piece::Bishop, 4, 4).unwrap();
+    assert!(outcome.is_drop());
+    assert_eq!(outcome.get_kif(None), "５五角打");
+    assert!(board.drop_piece_with_outcome(Piece::Rook, 4, 3).is_none());
+    assert!(board.make_move_with_outcome(Position::new(2, 0), Position::new(3, 0), false).is_none());
+}
+
+#[test]
+fn reports_check_and_game_end() {
+    // 頭金で詰み
+    let mut board = Board::from_sfen("4k4/9/4P4/9/9/9/9/9/4K4 b G 1").unwrap();
+    let outcome = board.drop_piece_with_outcome(Piece::Gold, 7, 4).unwrap();
+    assert!(outcome.is_check());
+    assert!(outcome.get_status() == GameStatus::Checkmate);
+    assert!(outcome.get_winner() == Some(Player::Black));
+    assert!(board.game_status() == GameStatus::Checkmate);
+
+    // 王手だが逃げられる
+    let mut board = Board::from_sfen("4k4/9/9/9/9/9/9/9/4K3R b - 1").unwrap();
+    let outcome = board.make_move_with_outcome(Position::new(0, 8), Position::new(8, 8), false).unwrap();
+    assert!(outcome.is_check());
+    assert!(outcome.get_status() == GameStatus::Ongoing);
+    assert!(outcome.get_winner().is_none());
+
+    // 玉を取った場合
+    let mut board = Board::from_sfen("4k4/9/9/9/9/9/9/9/4R3K b - 1").unwrap();
+    let outcome = board.make_move_with_outcome(Position::new(0, 4), Position::new(8, 4), false).unwrap();
+    assert!(outcome.get_captured_piece() == Piece::King);
+    assert!(outcome.get_hand_piece() == Piece::Empty);
+    assert!(outcome.get_status() == GameStatus::KingCaptured);
+    assert!(board.get_winner() == Some(Player::Black));
+}
+
+#[test]
+fn rejects_pawn_drop_mate() {
+    // １二歩打は１三金に支えられ、玉の逃げ場もないので打ち歩詰め
+    let mut board = Board::from_sfen("7nk/7s1/8G/9/9/9/9/9/K8 b P 1").unwrap();
+    assert!(board.drop_piece_with_outcome(Piece::Pawn, 7, 8).is_none());
+    assert!(!board.drop_piece(Piece::Pawn, 7, 8));
+    assert_eq!(board.get_captured_piece_count(Player::Black, Piece::Pawn), 1);
+    assert!(board.get_current_player() == Player::Black);
+
+    // 詰まない歩打ちは指せる
+    assert!(board.drop_piece_with_outcome(Piece::Pawn, 4, 4).is_some());
+}
+
+#[test]
+fn rejects_moves_leaving_own_king_in_check() {
+    // ５八の金は５五の飛車にピンされている
+    let mut board = Board::from_sfen("4k4/9/9/9/4r4/9/9/4G4/4K4 b - 1").unwrap();
+    assert!(board.make_move_with_outcome(Position::new(1, 4), Position::new(1, 3), false).is_none());
+    assert!(board.make_move_with_outcome(Position::new(1, 4), Position::new(2, 4), false).is_some());
+
+    // 相手の飛車が利いているマスに玉は動けない
+    let mut board = Board::from_sfen("4k4/9/9/9/3r5/9/9/9/4K4 b - 1").unwrap();
+    assert!(board.make_move_with_outcome(Position::new(0, 4), Position::new(1, 3), false).is_none());
+    assert!(!board.make_move(Position::new(0, 4), Position::new(1, 3)));
+    assert!(board.make_move_with_outcome(Position::new(0, 4), Position::new(1, 4), false).is_some());
+}
+
+#[test]
+fn highlighted_moves_match_playable_moves() {
+    // ５八の飛車は５五の飛車にピンされていて、５筋の上でしか動けない
+    let board = Board::from_sfen("4k4/9/9/9/4r4/9/9/4R4/4K4 b - 1").unwrap();
+    let from = Position::new(1, 4);
+    let moves = board.get_valid_moves(from);
+    assert!(moves.iter().all(|to| to.column == 4));
+    assert!(moves.contains(&Position::new(4, 4)));
+    assert!(!board.is_valid_move(from, Position::new(1, 0)));
+    assert!(board.get_valid_moves_by_coords(1, 4) == moves);
+
+    // 玉は相手の飛車が利いているマスを示さない
+    let board = Board::from_sfen("4k4/9/9/9/3r5/9/9/9/4K4 b P 1").unwrap();
+    assert!(!board.get_valid_moves(Position::new(0, 4)).contains(&Position::new(1, 3)));
+    assert!(board.get_valid_moves(Position::new(0, 4)).contains(&Position::new(1, 4)));
+
+    // 打ち歩詰めのマスには打てない
+    let board = Board::from_sfen("7nk/7s1/8G/9/9/9/9/9/K8 b P 1").unwrap();
+    assert!(!board.can_drop_piece(Piece::Pawn, 7, 8));
+    assert!(board.can_drop_piece(Piece::Pawn, 4, 4));
+}
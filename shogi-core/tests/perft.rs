@@ -0,0 +1,62 @@
+use shogi_core::Board;
+
+// 初期局面からの既知の末端局面数
+#[test]
+fn perft_initial_position() {
+    let board = Board::new();
+    assert_eq!(board.perft(1), 30);
+    assert_eq!(board.perft(2), 900);
+    assert_eq!(board.perft(3), 25_470);
+    assert_eq!(board.perft(4), 719_731);
+}
+
+#[test]
+#[ignore = "時間がかかるため cargo test --release -- --ignored で実行する"]
+fn perft_initial_position_depth_5() {
+    assert_eq!(Board::new().perft(5), 19_861_490);
+}
+
+// 合法手が最も多い局面として知られる593手の局面
+#[test]
+fn perft_maximum_moves_position() {
+    let board = Board::from_sfen("R8/2K1S1SSk/4B4/9/9/9/9/9/1L1L1L3 b RBGSNLP3g3n17p 1").unwrap();
+    assert_eq!(board.perft(1), 593);
+}
+
+// 成り・打ち駒・王手が入り組んだ「祭り」局面
+#[test]
+fn perft_matsuri_position() {
+    let board = Board::from_sfen("l6nl/5+P1gk/2np1S3/p1p4Pp/3P2Sp1/1PPb2P1P/P5GS1/R8/LN4bKL w RGgsn5p 1").unwrap();
+    assert_eq!(board.perft(1), 207);
+    assert_eq!(board.perft(2), 28_684);
+}
+
+#[test]
+#[ignore = "時間がかかるため cargo test --release -- --ignored で実行する"]
+fn perft_matsuri_position_depth_3() {
+    let board = Board::from_sfen("l6nl/5+P1gk/2np1S3/p1p4Pp/3P2Sp1/1PPb2P1P/P5GS1/R8/LN4bKL w RGgsn5p 1").unwrap();
+    assert_eq!(board.perft(3), 4_809_015);
+}
+
+#[test]
+fn divide_sums_to_perft() {
+    let board = Board::new();
+    let lines = board.divide(2);
+    assert_eq!(lines.len(), 30);
+    assert!(lines.contains(&"7g7f: 30".to_string()));
+    let total: u64 = lines
+        .iter()
+        .map(|line| line.rsplit(": ").next().unwrap().parse::<u64>().unwrap())
+        .sum();
+    assert_eq!(total, board.perft(2));
+}
+
+#[test]
+fn sfen_round_trip() {
+    let sfen = "l6nl/5+P1gk/2np1S3/p1p4Pp/3P2Sp1/1PPb2P1P/P5GS1/R8/LN4bKL w RGgsn5p 1";
+    assert_eq!(Board::from_sfen(sfen).unwrap().to_sfen(), sfen);
+    assert_eq!(
+        Board::new().to_sfen(),
+        "lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1"
+    );
+}
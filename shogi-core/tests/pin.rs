@@ -0,0 +1,31 @@
+use shogi_core::{Board, Player, Position};
+
+#[test]
+fn finds_pins_to_king() {
+    // ５九の玉と５一の飛車の間に金
+    let board = Board::from_sfen("4r3k/9/9/9/9/9/2B6/4G4/4K4 b - 1").unwrap();
+    let pins = board.pinned_pieces(Player::Black);
+    assert_eq!(pins.len(), 1);
+    assert!(pins[0].get_blocker() == Position::new(1, 4));
+    assert!(pins[0].get_slider() == Position::new(8, 4));
+    assert!(pins[0].get_target() == Position::new(0, 4));
+    assert_eq!(pins[0].get_line().len(), 8);
+
+    // 駒が2枚あればピンではない
+    let board = Board::from_sfen("4r3k/9/9/9/9/9/4P4/4G4/4K4 b - 1").unwrap();
+    assert!(board.pinned_pieces(Player::Black).is_empty());
+    // 相手の駒が間にあってもピンではない
+    let board = Board::from_sfen("4r3k/9/9/9/9/9/9/4g4/4K4 b - 1").unwrap();
+    assert!(board.pinned_pieces(Player::Black).is_empty());
+}
+
+#[test]
+fn finds_discovered_attacks() {
+    // 香の前の桂が跳ねると５一の玉に利きが通る（開き王手）。角の前の歩がどくと７四の飛車に利きが通る
+    let board = Board::from_sfen("4k4/9/9/2r6/9/9/4NP3/9/4L2BK b - 1").unwrap();
+    let lines = board.discovered_attacks(Player::Black);
+    assert_eq!(lines.len(), 2);
+    assert!(lines.iter().any(|line| line.get_blocker() == Position::new(2, 4) && line.get_target() == Position::new(8, 4)));
+    assert!(lines.iter().any(|line| line.get_blocker() == Position::new(2, 5) && line.get_slider() == Position::new(0, 7)));
+    assert!(board.discovered_attacks(Player::White).is_empty());
+}
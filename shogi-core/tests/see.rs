@@ -0,0 +1,41 @@
+use shogi_core::{Board, Move, Piece, Player, Position};
+
+fn board_move(from: (i32, i32), to: (i32, i32), piece: Piece) -> Move {
+    Move::new(Position::new(from.0, from.1), Position::new(to.0, to.1), piece, false)
+}
+
+#[test]
+fn static_exchange_evaluation() {
+    // 歩に守られた歩を飛車で取ると、飛車を取り返される
+    let board = Board::from_sfen("4k4/9/9/4p4/4p4/9/9/4R4/4K4 b - 1").unwrap();
+    assert_eq!(board.see(board_move((1, 4), (4, 4), Piece::Rook)), 100 - 1100);
+
+    // 守りのない歩はただで取れる
+    let board = Board::from_sfen("4k4/9/9/9/4p4/9/9/4R4/4K4 b - 1").unwrap();
+    assert_eq!(board.see(board_move((1, 4), (4, 4), Piece::Rook)), 100);
+
+    // 歩で取ると金で取り返せるが、後ろの飛車に金を取られるので取り返さない
+    let board = Board::from_sfen("4k4/9/9/4g4/4p4/4P4/9/4R4/4K4 b - 1").unwrap();
+    assert_eq!(board.see(board_move((3, 4), (4, 4), Piece::Pawn)), 100);
+
+    // 玉の隣に打った角はただで取られる
+    let board = Board::from_sfen("4k4/9/9/9/9/9/9/9/4K4 b B 1").unwrap();
+    assert_eq!(board.see(Move::new_drop(Piece::Bishop, Position::new(7, 4))), -950);
+    // 守りがあれば玉では取れない
+    let board = Board::from_sfen("4k4/9/9/9/9/9/9/4R4/4K4 b B 1").unwrap();
+    assert_eq!(board.see(Move::new_drop(Piece::Bishop, Position::new(7, 4))), 0);
+}
+
+#[test]
+fn detects_hanging_pieces() {
+    assert!(Board::new().hanging_pieces(Player::Black).is_empty());
+
+    // 歩に当たっている金は浮き駒。金に当たっている歩も守りがない
+    let board = Board::from_sfen("4k4/9/9/9/9/4p4/4G4/9/4K4 b - 1").unwrap();
+    assert!(board.hanging_pieces(Player::Black) == vec![Position::new(2, 4)]);
+    assert!(board.hanging_pieces(Player::White) == vec![Position::new(3, 4)]);
+
+    // 歩を守れば、金と歩の取り合いでは歩は浮き駒ではない
+    let board = Board::from_sfen("4k4/9/9/9/4p4/4p4/4G4/9/4K4 b - 1").unwrap();
+    assert!(board.hanging_pieces(Player::White).is_empty());
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "serde")]
+
+use shogi_core::{Board, Piece, Player};
+
+#[test]
+fn board_round_trip() {
+    let board = Board::from_sfen("lnsgk2nl/1r4gs1/p1pppp1pp/1p4p2/7P1/2P6/PP1PPPP1P/1SG4R1/LN2KGSNL b Bb 1").unwrap();
+    let json = serde_json::to_string(&board).unwrap();
+    let restored: Board = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.to_sfen(), board.to_sfen());
+}
+
+// フロントエンドの RecognizedPosition と同じ形を読み込める
+#[test]
+fn reads_recognized_position_shape() {
+    let mut rows = vec![vec![serde_json::Value::Null; 9]; 9];
+    rows[0][4] = serde_json::json!({ "piece": "king", "side": "black" });
+    rows[8][4] = serde_json::json!({ "piece": "king", "side": "white" });
+    rows[4][4] = serde_json::json!({ "piece": "promoted_silver", "side": "white" });
+    let value = serde_json::json!({
+        "turn": "white",
+        "board": rows,
+        "captured": {
+            "black": { "pawn": 2, "lance": 0, "knight": 0, "silver": 0, "gold": 1, "bishop": 0, "rook": 0, "king": 0 },
+            "white": { "rook": 1 },
+        },
+    });
+
+    let board: Board = serde_json::from_value(value).unwrap();
+    assert_eq!(board.to_sfen(), "4k4/9/9/9/4+s4/9/9/9/4K4 w G2Pr 1");
+    assert!(board.get_current_player() == Player::White);
+    assert_eq!(board.get_piece_box_count(Piece::King), 0);
+
+    let json = serde_json::to_value(&board).unwrap();
+    assert_eq!(json["board"][4][4]["piece"], "promoted_silver");
+    assert_eq!(json["captured"]["black"]["pawn"], 2);
+    assert!(json["board"][3][3].is_null());
+}
+
+#[test]
+fn rejects_malformed_board() {
+    let value = serde_json::json!({
+        "turn": "black",
+        "board": [[null]],
+        "captured": { "black": {}, "white": {} },
+    });
+    assert!(serde_json::from_value::<Board>(value).is_err());
+}
+
+#[test]
+fn move_round_trip() {
+    let board = Board::new();
+    let moves = board.generate_legal_moves();
+    let json = serde_json::to_string(&moves).unwrap();
+    let restored: Vec<shogi_core::Move> = serde_json::from_str(&json).unwrap();
+    assert!(restored == moves);
+}
@@ -0,0 +1,68 @@
+use shogi_core::{Board, Piece, Player, SvgOptions};
+
+fn count(svg: &str, pattern: &str) -> usize {
+    svg.matches(pattern).count()
+}
+
+#[test]
+fn draws_all_pieces_and_coordinates() {
+    let svg = Board::new().to_svg(&SvgOptions::new());
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert_eq!(count(&svg, "class=\"piece\""), 40);
+    // 後手の駒だけが逆さになる
+    assert_eq!(count(&svg, "rotate(180"), 20);
+    assert!(svg.contains(">９</text>") && svg.contains(">九</text>"));
+    assert_eq!(count(&svg, "class=\"last-move\""), 0);
+    assert_eq!(count(&svg, "class=\"arrow\""), 0);
+}
+
+#[test]
+fn flipped_board_turns_the_other_side() {
+    let mut board = Board::new();
+    board.clear_board();
+    board.set_piece_by_coords(0, 4, Piece::King, Player::Black);
+    let mut options = SvgOptions::new();
+    let normal = board.to_svg(&options);
+    options.set_flipped(true);
+    let flipped = board.to_svg(&options);
+
+    // 先手玉（５九）は通常は下端、反転すると上端に逆さで描く
+    assert!(normal.contains(r#"x="230" y="370""#));
+    assert!(!normal.contains("rotate(180"));
+    assert!(flipped.contains(r#"x="230" y="50""#));
+    assert!(flipped.contains("rotate(180 230 50)"));
+}
+
+#[test]
+fn highlights_last_move_and_draws_arrows() {
+    let mut board = Board::new();
+    let mv = board.move_from_usi("7g7f").unwrap();
+    assert!(mv.get_piece() == Piece::Pawn);
+    board.make_move_with_promotion(mv.get_from(), mv.get_to(), false);
+
+    let mut options = SvgOptions::new();
+    options.set_last_move(&board.move_from_usi("7g7f").unwrap());
+    options.add_arrow(2, 7, 3, 7);
+    let svg = board.to_svg(&options);
+    assert_eq!(count(&svg, "class=\"last-move\""), 2);
+    assert_eq!(count(&svg, "class=\"arrow\""), 1);
+
+    // 打つ手は移動先だけを塗る
+    options = SvgOptions::new();
+    options.set_last_move(&board.move_from_usi("P*5e").unwrap());
+    assert_eq!(count(&board.to_svg(&options), "class=\"last-move\""), 1);
+
+    assert!(board.move_from_usi("5e5d").is_err());
+    assert!(board.move_from_usi("K*5e+").is_err());
+}
+
+#[test]
+fn draws_hands_vertically() {
+    let board = Board::from_sfen("4k4/9/9/9/9/9/9/9/4K4 b R2G18p 1").unwrap();
+    let svg = board.to_svg(&SvgOptions::new());
+    assert_eq!(count(&svg, "class=\"hand\""), 2);
+    for c in ["☗", "☖", "飛", "金", "二", "歩", "十", "八"] {
+        assert!(svg.contains(&format!(">{}</text>", c)), "{}", c);
+    }
+}
@@ -0,0 +1,12 @@
+use shogi_core::Board;
+
+#[test]
+fn random_playout_is_reproducible_and_legal() {
+    assert_eq!(Board::random_playout(1, 0).to_sfen(), Board::new().to_sfen());
+    for seed in 0..20 {
+        let board = Board::random_playout(seed, 100);
+        assert_eq!(board.to_sfen(), Board::random_playout(seed, 100).to_sfen());
+        assert!(board.validate().iter().all(|issue| !issue.is_error()), "{}", board.to_sfen());
+        assert!(board.remaining_in_box().iter().all(|&count| count == 0));
+    }
+}
@@ -0,0 +1,83 @@
+use shogi_core::{Board, GameResult, Piece, Player, Position, Termination};
+
+#[test]
+fn resignation_is_written_for_each_format() {
+    // 64手目を指した後、先手が投了
+    let result = GameResult::new(Termination::Resign, 64, Player::Black);
+    assert!(result.get_winner() == Some(Player::White));
+    assert_eq!(result.get_kif_move_name(), "投了");
+    assert_eq!(result.get_kif_summary(), "まで64手で後手の勝ち");
+    assert_eq!(result.get_csa(), "%TORYO");
+    assert_eq!(result.get_jkf(), "{\"special\":\"TORYO\"}");
+
+    let result = GameResult::new(Termination::TimeUp, 31, Player::White);
+    assert_eq!(result.get_kif_summary(), "まで31手で時間切れにより先手の勝ち");
+    assert_eq!(result.get_csa(), "%TIME_UP");
+
+    let result = GameResult::new(Termination::EnteringKing, 200, Player::Black);
+    assert!(result.get_winner() == Some(Player::Black));
+    assert_eq!(result.get_csa(), "%KACHI");
+
+    // 詰みも勝った側を書く
+    let result = GameResult::new(Termination::Checkmate, 57, Player::White);
+    assert_eq!(result.get_kif_move_name(), "詰み");
+    assert_eq!(result.get_kif_summary(), "まで57手で先手の勝ち");
+    assert_eq!(result.get_csa(), "%TSUMI");
+}
+
+#[test]
+fn jkf_special_is_a_json_move() {
+    let terminations = [
+        Termination::Resign,
+        Termination::Checkmate,
+        Termination::Repetition,
+        Termination::Impasse,
+        Termination::IllegalWin,
+        Termination::IllegalLoss,
+        Termination::TimeUp,
+        Termination::Interrupt,
+        Termination::EnteringKing,
+        Termination::Try,
+    ];
+    for termination in terminations {
+        let result = GameResult::new(termination, 10, Player::Black);
+        let value: serde_json::Value = serde_json::from_str(&result.get_jkf()).unwrap();
+        let special = value["special"].as_str().unwrap();
+        // CSAと同じ名前から "%" を除いたもの
+        assert_eq!(format!("%{}", special), result.get_csa());
+        assert_eq!(value.as_object().unwrap().len(), 1);
+    }
+}
+
+#[test]
+fn draws_and_illegal_moves() {
+    let result = GameResult::new(Termination::Repetition, 80, Player::Black);
+    assert!(result.get_winner().is_none());
+    assert_eq!(result.get_kif_summary(), "まで80手で千日手");
+    assert_eq!(result.get_csa(), "%SENNICHITE");
+
+    // 反則した側の符号が付く
+    let result = GameResult::new(Termination::IllegalWin, 41, Player::Black);
+    assert!(result.get_winner() == Some(Player::Black));
+    assert_eq!(result.get_kif_summary(), "まで41手で先手の反則勝ち");
+    assert_eq!(result.get_csa(), "%-ILLEGAL_ACTION");
+
+    let result = GameResult::new(Termination::IllegalLoss, 41, Player::Black);
+    assert_eq!(result.get_kif_summary(), "まで41手で先手の反則負け");
+    assert_eq!(result.get_jkf(), "{\"special\":\"+ILLEGAL_ACTION\"}");
+}
+
+#[test]
+fn termination_from_board() {
+    assert!(Board::new().termination().is_none());
+
+    // 頭金で詰み
+    let mut board = Board::from_sfen("4k4/9/4P4/9/9/9/9/9/4K4 b G 1").unwrap();
+    board.drop_piece(Piece::Gold, 7, 4);
+    assert!(board.termination() == Some(Termination::Checkmate));
+
+    let mut board = Board::from_sfen("9/4K4/9/9/9/9/9/9/k8 b - 1").unwrap();
+    board.set_try_rule(true);
+    board.make_move(Position::new(7, 4), Position::new(8, 4));
+    assert!(board.termination() == Some(Termination::Try));
+}
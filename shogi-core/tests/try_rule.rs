@@ -0,0 +1,34 @@
+use shogi_core::{Board, GameStatus, Player, Position};
+
+#[test]
+fn king_reaching_opponent_king_square_wins() {
+    let mut board = Board::from_sfen("9/4K4/9/9/9/9/9/9/k8 b - 1").unwrap();
+    let mut without_rule = board.clone();
+    board.set_try_rule(true);
+    assert!(board.is_try_rule() && board.clone().is_try_rule());
+
+    let outcome = board.make_move_with_outcome(Position::new(7, 4), Position::new(8, 4), false).unwrap();
+    assert!(outcome.get_status() == GameStatus::Try);
+    assert!(outcome.get_winner() == Some(Player::Black));
+    assert!(board.get_winner() == Some(Player::Black));
+
+    // トライルールを使わない対局では続く
+    without_rule.make_move(Position::new(7, 4), Position::new(8, 4));
+    assert!(without_rule.game_status() == GameStatus::Ongoing);
+}
+
+#[test]
+fn attacked_try_square_does_not_win() {
+    // ４一の金が５一に利いている
+    let mut board = Board::from_sfen("5g3/4K4/9/9/9/9/9/9/k8 b - 1").unwrap();
+    board.set_try_rule(true);
+    board.make_move(Position::new(7, 4), Position::new(8, 4));
+    assert!(board.game_status() == GameStatus::Ongoing);
+
+    // 後手は５九を目指す
+    let mut board = Board::from_sfen("8K/9/9/9/9/9/9/4k4/9 w - 1").unwrap();
+    board.set_try_rule(true);
+    board.make_move(Position::new(1, 4), Position::new(0, 4));
+    assert!(board.game_status() == GameStatus::Try);
+    assert!(board.get_winner() == Some(Player::White));
+}
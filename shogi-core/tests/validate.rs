@@ -0,0 +1,37 @@
+use shogi_core::{Board, IssueKind};
+
+fn kinds(sfen: &str) -> Vec<IssueKind> {
+    Board::from_sfen(sfen).unwrap().validate().iter().map(|issue| issue.get_kind()).collect()
+}
+
+#[test]
+fn initial_position_is_valid() {
+    assert!(Board::new().validate().is_empty());
+}
+
+#[test]
+fn detects_piece_placement_issues() {
+    // 二歩と1段目の歩
+    let issues = kinds("4k3P/9/9/9/9/9/4P4/9/4KP3 b - 1");
+    assert!(issues.contains(&IssueKind::DeadPiece));
+    assert!(!issues.contains(&IssueKind::DoublePawn));
+    let issues = kinds("4k4/9/9/9/4P4/9/4P4/9/4K4 b - 1");
+    assert_eq!(issues, vec![IssueKind::DoublePawn]);
+
+    // 成り駒と持ち駒を含めて角が3枚
+    let issues = kinds("4k4/9/9/9/4+B4/9/9/9/4K4 b 2B 1");
+    assert_eq!(issues, vec![IssueKind::PieceCountExceeded]);
+
+    let issues = kinds("4k4/9/9/9/4K4/9/9/9/4K4 b - 1");
+    assert_eq!(issues, vec![IssueKind::KingCount]);
+}
+
+#[test]
+fn detects_check_issues() {
+    // 手番でない後手の玉に王手
+    assert_eq!(kinds("4k4/9/9/9/9/9/9/9/4R3K b - 1"), vec![IssueKind::OpponentInCheck]);
+    // 手番側への王手は問題ない
+    assert!(kinds("4k4/9/9/9/9/9/9/9/R3K3r b - 1").is_empty());
+    // 両方の玉に王手
+    assert_eq!(kinds("4k4/9/9/9/4R4/9/9/9/r3K4 b - 1"), vec![IssueKind::BothKingsInCheck]);
+}